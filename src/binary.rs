@@ -0,0 +1,267 @@
+//! The compact binary format for simple fields.
+//!
+//! Serializing a field with Serde stores a whole enumeration for every tile, which is too large for big fields and network synchronization. This format stores every tile of a [`SimpleField`][sf] in 3 bits instead, since the numbers on open tiles can be recomputed from the mines when loading. All multibyte integers are little-endian. The layout is as follows:
+//!
+//! | Size | Contents |
+//! |------|----------|
+//! | 4 bytes | The magic bytes `SWPR` |
+//! | 1 byte | The version of the format: `2` if the field has void tiles, `1` otherwise |
+//! | 8 bytes | The width of the field |
+//! | 8 bytes | The height of the field |
+//! | ⌈3 × width × height ÷ 8⌉ bytes | The tiles in row-major order, packed starting from the least significant bit |
+//! | 4 bytes | The CRC-32 checksum of everything above |
+//!
+//! Every tile is stored as one of the following codes:
+//!
+//! | Code | Tile state |
+//! |------|------------|
+//! | `0` | `ClosedEmpty(Flag::NotFlagged)` |
+//! | `1` | `ClosedEmpty(Flag::Flagged)` |
+//! | `2` | `ClosedEmpty(Flag::Custom(()))` |
+//! | `3` | `OpenEmpty` or `OpenNumber`, depending on the neighboring mines |
+//! | `4` | `Mine(Flag::NotFlagged)` |
+//! | `5` | `Mine(Flag::Flagged)` |
+//! | `6` | `Mine(Flag::Custom(()))` |
+//! | `7` | `Void`, only in version `2` |
+//!
+//! # Usage
+//! ```
+//! # use sweeper::{SimpleField, TileState, Flag};
+//! # use core::num::NonZeroUsize;
+//! #
+//! let mut field = SimpleField::empty([NonZeroUsize::new(30).unwrap(), NonZeroUsize::new(16).unwrap()]);
+//! field[[4, 2]].state = TileState::Mine(Flag::Flagged);
+//! field[[5, 2]].state = TileState::OpenEmpty;
+//!
+//! let bytes = field.to_bytes();
+//! assert_eq!(bytes.len(), 4 + 1 + 8 + 8 + 180 + 4);
+//! let decoded = SimpleField::from_bytes(&bytes).unwrap();
+//! assert_eq!(decoded[[4, 2]].state, TileState::Mine(Flag::Flagged));
+//! // The number is recomputed from the mines.
+//! assert_eq!(format!("{:?}", decoded[[5, 2]].state), "OpenNumber(1)");
+//! ```
+//!
+//! [sf]: ../type.SimpleField.html "SimpleField — a field without any custom tile data or custom flags"
+
+use core::{
+    convert::TryFrom,
+    fmt::{self, Formatter},
+    num::{NonZeroUsize, NonZeroU8},
+};
+use alloc::vec::Vec;
+use crate::{SimpleField, Tile, TileState, Flag};
+
+const MAGIC: [u8; 4] = *b"SWPR";
+const VERSION: u8 = 2;
+/// The version written for fields without void tiles, which can be read by decoders predating them.
+const VERSION_WITHOUT_VOIDS: u8 = 1;
+const BITS_PER_TILE: usize = 3;
+const HEADER_SIZE: usize = MAGIC.len() + 1 + 8 + 8;
+const CHECKSUM_SIZE: usize = 4;
+
+impl SimpleField {
+    /// Encodes the field in the [compact binary format][binary].
+    ///
+    /// [binary]: binary/index.html "binary — the compact binary format for simple fields"
+    #[must_use = "this performs a memory allocation and traverses the entire field"]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let [width, height] = self.dimensions();
+        let area = width.get() * height.get();
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + packed_size(area) + CHECKSUM_SIZE);
+        bytes.extend_from_slice(&MAGIC);
+        let has_voids = self.all_tiles().any(|tile| tile.state.is_void());
+        bytes.push(if has_voids {VERSION} else {VERSION_WITHOUT_VOIDS});
+        bytes.extend_from_slice(&(width.get() as u64).to_le_bytes());
+        bytes.extend_from_slice(&(height.get() as u64).to_le_bytes());
+
+        bytes.resize(HEADER_SIZE + packed_size(area), 0);
+        let packed = &mut bytes[HEADER_SIZE..];
+        for (index, tile) in self.all_tiles().enumerate() {
+            let code: u16 = match tile.state {
+                TileState::ClosedEmpty(Flag::NotFlagged) => 0,
+                TileState::ClosedEmpty(Flag::Flagged) => 1,
+                TileState::ClosedEmpty(Flag::Custom(())) => 2,
+                TileState::OpenEmpty | TileState::OpenNumber(_) => 3,
+                TileState::Mine(Flag::NotFlagged) => 4,
+                TileState::Mine(Flag::Flagged) => 5,
+                TileState::Mine(Flag::Custom(())) => 6,
+                TileState::Void => 7,
+            };
+            let bit = index * BITS_PER_TILE;
+            // A code can span two bytes, so it's written as a 16-bit window.
+            let [low, high] = (code << (bit % 8)).to_le_bytes();
+            packed[bit / 8] |= low;
+            if high != 0 {
+                packed[bit / 8 + 1] |= high;
+            }
+        }
+        let checksum = crc32(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+    /// Decodes a field from the [compact binary format][binary], recomputing the numbers on the open tiles.
+    ///
+    /// # Errors
+    /// Returns an error if the data is not a valid field in the binary format. See [`DecodeError`][de] for the exact checks performed.
+    ///
+    /// [binary]: binary/index.html "binary — the compact binary format for simple fields"
+    /// [de]: binary/enum.DecodeError.html "DecodeError — the error type for decoding a field from the compact binary format"
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if bytes.len() < MAGIC.len() + 1 {
+            return Err(DecodeError::Truncated);
+        }
+        if bytes[..MAGIC.len()] != MAGIC {
+            return Err(DecodeError::BadMagic);
+        }
+        let version = bytes[MAGIC.len()];
+        if version != VERSION && version != VERSION_WITHOUT_VOIDS {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+        if bytes.len() < HEADER_SIZE {
+            return Err(DecodeError::Truncated);
+        }
+        let read_dimension = |offset: usize| {
+            let mut le_bytes = [0; 8];
+            le_bytes.copy_from_slice(&bytes[offset..offset + 8]);
+            usize::try_from(u64::from_le_bytes(le_bytes)).ok()
+                .and_then(NonZeroUsize::new)
+                .ok_or(DecodeError::InvalidDimensions)
+        };
+        let dimensions = [read_dimension(MAGIC.len() + 1)?, read_dimension(MAGIC.len() + 9)?];
+        let area = dimensions[0].get().checked_mul(dimensions[1].get())
+            .filter(|&area| area <= usize::MAX / BITS_PER_TILE)
+            .ok_or(DecodeError::InvalidDimensions)?;
+
+        // Check the length before allocating anything, so that a corrupt header can't request a huge allocation.
+        let total_size = HEADER_SIZE + packed_size(area) + CHECKSUM_SIZE;
+        if bytes.len() < total_size {
+            return Err(DecodeError::Truncated);
+        }
+        if bytes.len() > total_size {
+            return Err(DecodeError::TrailingBytes);
+        }
+        let (data, checksum) = bytes.split_at(total_size - CHECKSUM_SIZE);
+        if crc32(data).to_le_bytes() != checksum {
+            return Err(DecodeError::ChecksumMismatch);
+        }
+
+        let packed = &data[HEADER_SIZE..];
+        let mut storage = Vec::with_capacity(area);
+        for index in 0..area {
+            let bit = index * BITS_PER_TILE;
+            let window = u16::from_le_bytes([packed[bit / 8], packed.get(bit / 8 + 1).copied().unwrap_or(0)]);
+            let state = match (window >> (bit % 8)) & 0b111 {
+                0 => TileState::ClosedEmpty(Flag::NotFlagged),
+                1 => TileState::ClosedEmpty(Flag::Flagged),
+                2 => TileState::ClosedEmpty(Flag::Custom(())),
+                // The number is recomputed once all mines are known.
+                3 => TileState::OpenEmpty,
+                4 => TileState::Mine(Flag::NotFlagged),
+                5 => TileState::Mine(Flag::Flagged),
+                6 => TileState::Mine(Flag::Custom(())),
+                7 if version == VERSION => TileState::Void,
+                _ => return Err(DecodeError::InvalidTile {index}),
+            };
+            storage.push(Tile {state, payload: ()});
+        }
+        // The storage has exactly as many tiles as the dimensions require, so this never fails.
+        let mut field = Self::from_dimensions_and_storage(dimensions, storage)
+            .ok_or(DecodeError::InvalidDimensions)?;
+        recompute_numbers(&mut field);
+        Ok(field)
+    }
+}
+
+/// Returns the amount of bytes needed to store the specified amount of tiles.
+const fn packed_size(area: usize) -> usize {
+    (area * BITS_PER_TILE + 7) / 8
+}
+
+/// Replaces every open tile with an `OpenEmpty` or `OpenNumber` tile, depending on the amount of neighboring mines.
+fn recompute_numbers(field: &mut SimpleField) {
+    let [width, height] = field.dimensions();
+    let (width, height) = (width.get(), height.get());
+    for y in 0..height {
+        for x in 0..width {
+            if !field[[x, y]].state.is_open() {continue}
+            field[[x, y]].state = NonZeroU8::new(field.count_neighboring_mines([x, y]))
+                .map_or(TileState::OpenEmpty, TileState::OpenNumber);
+        }
+    }
+}
+
+/// The lookup table for the CRC-32 checksum with the IEEE polynomial, the one used by zlib, PNG and Ethernet.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0_u32; 256];
+    let mut i = 0_u32;
+    while i < 256 {
+        let mut crc = i;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {(crc >> 1) ^ 0xEDB8_8320} else {crc >> 1};
+            bit += 1;
+        }
+        table[i as usize] = crc;
+        i += 1;
+    }
+    table
+};
+/// Calculates the CRC-32 checksum of the specified bytes.
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0_u32, |crc, &byte| {
+        CRC32_TABLE[((crc ^ u32::from(byte)) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+/// The error type for decoding a field from the [compact binary format][binary].
+///
+/// [binary]: index.html "binary — the compact binary format for simple fields"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DecodeError {
+    /// The data ends before the end of the field.
+    Truncated,
+    /// There is more data after the end of the field.
+    TrailingBytes,
+    /// The data doesn't start with the magic bytes of the format.
+    BadMagic,
+    /// The data was written by a newer version of the format, which is not supported.
+    UnsupportedVersion(u8),
+    /// The width or height of the field is zero or too big for the platform.
+    InvalidDimensions,
+    /// The checksum doesn't match the data, which means that the data is corrupt.
+    ChecksumMismatch,
+    /// The tile with the specified index in row-major order has a code which doesn't correspond to any tile state.
+    InvalidTile {
+        /// The index of the tile in row-major order.
+        index: usize,
+    },
+}
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(f,
+                "the data ends before the end of the field"
+            ),
+            Self::TrailingBytes => write!(f,
+                "there is more data after the end of the field"
+            ),
+            Self::BadMagic => write!(f,
+                "the data is not a field in the binary format"
+            ),
+            Self::UnsupportedVersion(version) => write!(f,
+                "version {version} of the binary format is not supported"
+            ),
+            Self::InvalidDimensions => write!(f,
+                "the dimensions of the field are zero or too big"
+            ),
+            Self::ChecksumMismatch => write!(f,
+                "the checksum doesn't match the data"
+            ),
+            Self::InvalidTile {index} => write!(f,
+                "tile {index} has an invalid code"
+            ),
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
@@ -0,0 +1,639 @@
+//! Three-dimensional fields.
+//!
+//! A [`CubeField`][cf] is a volume of [tiles][tile] made of several layers stacked on top of each other, where every tile touches the 26 tiles in the 3x3x3 cube around it: 8 on its own layer and 9 on each of the layers above and below. Every layer is stored as a regular [`Field`][field], so the layers can be inspected and modified with the row, column and tile iterators of regular fields.
+//!
+//! # Usage
+//! ```
+//! # use sweeper::{CubeField, TileState, Flag, ClickOutcome};
+//! # use core::num::NonZeroUsize;
+//! #
+//! let mut field = CubeField::<(), ()>::empty([NonZeroUsize::new(4).unwrap(); 3]);
+//! field[[0, 0, 0]].state = TileState::Mine(Flag::NotFlagged);
+//! // The mine is counted by the tiles on the layer below it as well.
+//! assert_eq!(field.count_neighboring_mines([1, 0, 0]), 1);
+//! assert_eq!(field.count_neighboring_mines([1, 1, 1]), 1);
+//! assert_eq!(field.count_neighboring_mines([2, 2, 2]), 0);
+//!
+//! // Opening the far corner opens everything except the mine.
+//! let (opened, _) = field.open_clearing([3, 3, 3], true).unwrap();
+//! assert_eq!(opened, 63);
+//! assert!(field.solved());
+//! // Every layer is a regular field.
+//! assert_eq!(field.layer(0).row(0).filter(|tile| tile.state.is_mine()).count(), 1);
+//! ```
+//!
+//! [cf]: struct.CubeField.html "CubeField — a three-dimensional playfield"
+//! [tile]: ../struct.Tile.html "Tile — a tile on a Minesweeper field"
+//! [field]: ../struct.Field.html "Field — represents a playfield"
+
+use core::{
+    ops::{Index, IndexMut},
+    num::NonZeroUsize,
+    iter::FusedIterator,
+    slice,
+};
+use alloc::vec::Vec;
+#[cfg(feature = "generation")]
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "generation")]
+use rand_pcg::Pcg64;
+#[cfg(feature = "generation")]
+use crate::{SafeZone, GenerationError};
+use crate::{
+    Field, FieldCoordinates, Tile, TileState, ClickOutcome, Error, InvalidFieldError,
+    RowIterMut, ColumnIterMut, FieldRowsIterMut, FieldColumnsIterMut, EnumerateTilesMut,
+    grid::{self, Grid},
+};
+
+/// The dimensions of a three-dimensional field.
+///
+/// The elements specify the width (the number of columns), the height (the number of rows) and the depth (the number of layers), in that order.
+pub type CubeDimensions = [NonZeroUsize; 3];
+/// The coordinates of a tile on a three-dimensional field.
+///
+/// The elements specify the column, the row and the layer, in that order, all starting from zero.
+pub type CubeCoordinates = [usize; 3];
+/// The outcome of a [chord operation][m_chord] on a three-dimensional field.
+///
+/// The entries are the neighboring tiles in the order of their offsets, compared layer first, then row and then column: the 9 tiles on the layer above, row by row starting from top-left, then the 8 tiles on the same layer and then the 9 tiles on the layer below. This is the order of [`CubeNeighborLocations`][cnl].
+///
+/// [m_chord]: struct.CubeField.html#method.chord "chord — performs a chord operation on the specified tile"
+/// [cnl]: struct.CubeNeighborLocations.html "CubeNeighborLocations — iterates over the locations of the neighbors of a tile on a three-dimensional field"
+pub type CubeChordOutcome = [ClickOutcome; 26];
+
+/// Returns the location of the neighbor of the specified tile with the specified index in the order of [`CubeChordOutcome`][cco], or `None` if it's outside the field.
+///
+/// [cco]: type.CubeChordOutcome.html "CubeChordOutcome — the outcome of a chord operation on a three-dimensional field"
+fn cube_neighbor(dimensions: CubeDimensions, center: CubeCoordinates, index: usize) -> Option<CubeCoordinates> {
+    // The center of the 3x3x3 cube has index 13, and it's skipped.
+    let cell = if index < 13 {index} else {index + 1};
+    let mut location = [0; 3];
+    for (axis, digit) in [cell % 3, cell / 3 % 3, cell / 9].iter().enumerate() {
+        location[axis] = (center[axis] + digit).checked_sub(1)
+            .filter(|&coordinate| coordinate < dimensions[axis].get())?;
+    }
+    Some(location)
+}
+
+/// Iterates over the locations of the neighbors of a tile on a three-dimensional field.
+///
+/// The neighbors are produced in the order of [`CubeChordOutcome`][cco], skipping the ones outside the field. Just like [`NeighborLocations`][nl], the iterator doesn't borrow the field.
+///
+/// # Usage
+/// ```
+/// # use sweeper::CubeNeighborLocations;
+/// # use core::num::NonZeroUsize;
+/// #
+/// // Check every tile of every field up to 3x3x3 against the definition.
+/// for width in 1..=3 {
+///     for height in 1..=3 {
+///         for depth in 1..=3 {
+///             let size = |n: usize| NonZeroUsize::new(n).unwrap();
+///             let dimensions = [size(width), size(height), size(depth)];
+///             for z in 0..depth as isize {
+///                 for y in 0..height as isize {
+///                     for x in 0..width as isize {
+///                         let mut expected = Vec::new();
+///                         for dz in -1..=1 {
+///                             for dy in -1..=1 {
+///                                 for dx in -1..=1 {
+///                                     let [nx, ny, nz] = [x + dx, y + dy, z + dz];
+///                                     if (dx, dy, dz) == (0, 0, 0) || nx < 0 || ny < 0 || nz < 0 {continue}
+///                                     if nx < width as isize && ny < height as isize && nz < depth as isize {
+///                                         expected.push([nx as usize, ny as usize, nz as usize]);
+///                                     }
+///                                 }
+///                             }
+///                         }
+///                         let actual: Vec<_> = CubeNeighborLocations::new(dimensions, [x as usize, y as usize, z as usize]).collect();
+///                         assert_eq!(actual, expected);
+///                     }
+///                 }
+///             }
+///             assert_eq!(CubeNeighborLocations::new(dimensions, [0, 0, depth]).count(), 0);
+///         }
+///     }
+/// }
+/// ```
+///
+/// [cco]: type.CubeChordOutcome.html "CubeChordOutcome — the outcome of a chord operation on a three-dimensional field"
+/// [nl]: ../iter/struct.NeighborLocations.html "NeighborLocations — iterates over the locations of the neighbors of a tile"
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CubeNeighborLocations {
+    dimensions: CubeDimensions,
+    center: CubeCoordinates,
+    index: usize,
+}
+impl CubeNeighborLocations {
+    /// Creates an iterator over the neighbors of the specified tile on a three-dimensional field with the specified dimensions. If the tile itself is outside the field, the iterator is empty.
+    #[inline]
+    pub const fn new(dimensions: CubeDimensions, center: CubeCoordinates) -> Self {
+        let inside = center[0] < dimensions[0].get() && center[1] < dimensions[1].get() && center[2] < dimensions[2].get();
+        Self {dimensions, center, index: if inside {0} else {NUM_NEIGHBORS}}
+    }
+    /// Returns the location of the tile whose neighbors are being iterated over.
+    #[inline(always)]
+    pub const fn center(&self) -> CubeCoordinates {
+        self.center
+    }
+}
+impl Iterator for CubeNeighborLocations {
+    type Item = CubeCoordinates;
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < NUM_NEIGHBORS {
+            let index = self.index;
+            self.index += 1;
+            if let Some(location) = cube_neighbor(self.dimensions, self.center, index) {
+                return Some(location);
+            }
+        }
+        None
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(NUM_NEIGHBORS - self.index))
+    }
+}
+impl FusedIterator for CubeNeighborLocations {}
+
+/// The amount of tiles in a 3x3x3 cube without its center.
+const NUM_NEIGHBORS: usize = 26;
+
+/// A three-dimensional playfield.
+///
+/// This works just like a regular [`Field`][field], except that the tiles are arranged in a volume and every tile has up to 26 neighbors, as described in the [module-level documentation][cube]. Clearings are opened right away by [`open_clearing`][m_open_clearing] instead of through a separate clearing reference.
+///
+/// [field]: ../struct.Field.html "Field — represents a playfield"
+/// [cube]: index.html "cube — three-dimensional fields"
+/// [m_open_clearing]: #method.open_clearing "open_clearing — fully opens the clearing around the specified tile"
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CubeField<Ct, Cf> {
+    dimensions: CubeDimensions,
+    layers: Vec<Field<Ct, Cf>>,
+}
+impl<Ct, Cf> CubeField<Ct, Cf>
+where Ct: Default {
+    /// Creates an empty three-dimensional field filled with unopened tiles, with the given dimensions.
+    #[must_use = "this performs a memory allocation as big as the volume of the field"]
+    pub fn empty(dimensions: CubeDimensions) -> Self {
+        let layers = (0..dimensions[2].get())
+            .map(|_| Field::empty([dimensions[0], dimensions[1]]))
+            .collect();
+        Self {dimensions, layers}
+    }
+}
+impl<Ct, Cf> CubeField<Ct, Cf> {
+    /// Returns the width, height and depth of the field.
+    #[inline(always)]
+    pub const fn dimensions(&self) -> CubeDimensions {
+        self.dimensions
+    }
+    /// Returns `true` if the specified location is inside the field, `false` otherwise.
+    #[inline(always)]
+    pub const fn contains(&self, coordinates: CubeCoordinates) -> bool {
+        coordinates[0] < self.dimensions[0].get()
+            && coordinates[1] < self.dimensions[1].get()
+            && coordinates[2] < self.dimensions[2].get()
+    }
+    /// Returns the tile at the specified location, or `None` if the location is out of bounds.
+    #[inline]
+    pub fn get(&self, coordinates: CubeCoordinates) -> Option<&Tile<Ct, Cf>> {
+        self.layers.get(coordinates[2])?.get([coordinates[0], coordinates[1]])
+    }
+    /// Returns a mutable reference to the tile at the specified location, or `None` if the location is out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, coordinates: CubeCoordinates) -> Option<&mut Tile<Ct, Cf>> {
+        self.layers.get_mut(coordinates[2])?.get_mut([coordinates[0], coordinates[1]])
+    }
+    /// Returns the specified layer as a regular field, which can be traversed with its row, column and tile iterators.
+    ///
+    /// The numbers on the open tiles stay the ones for the three-dimensional field, so the methods of the regular field which look at the neighbors of a tile will give wrong results.
+    ///
+    /// # Panics
+    /// Panics if the specified layer is out of range.
+    #[inline]
+    #[cfg_attr(feature = "track_caller", track_caller)]
+    pub fn layer(&self, layer: usize) -> &Field<Ct, Cf> {
+        &self.layers[layer]
+    }
+    /// Returns mutable access to the tiles of the specified layer. See [`layer`][m_layer] for the caveats.
+    ///
+    /// Only the tiles of the layer can be modified through the returned reference, so the dimensions, topology and neighborhood of the layer always stay the ones of the three-dimensional field.
+    ///
+    /// # Panics
+    /// Panics if the specified layer is out of range.
+    ///
+    /// [m_layer]: #method.layer "layer — returns the specified layer as a regular field"
+    #[inline]
+    #[cfg_attr(feature = "track_caller", track_caller)]
+    pub fn layer_mut(&mut self, layer: usize) -> CubeLayerMut<'_, Ct, Cf> {
+        CubeLayerMut {field: &mut self.layers[layer]}
+    }
+    /// Returns an iterator over the layers of the field as regular fields, starting from the top one. See [`layer`][m_layer] for the caveats.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{CubeField, TileState, Flag};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let mut field = CubeField::<(), ()>::empty([NonZeroUsize::new(3).unwrap(); 3]);
+    /// field[[0, 2, 1]].state = TileState::Mine(Flag::NotFlagged);
+    /// field[[2, 2, 1]].state = TileState::Mine(Flag::NotFlagged);
+    /// let mines_per_layer: Vec<_> = field.layers().map(|layer| layer.mines().count()).collect();
+    /// assert_eq!(mines_per_layer, [0, 2, 0]);
+    /// // The rows and columns of a layer are available as usual.
+    /// assert_eq!(field.layer(1).column(2).filter(|tile| tile.state.is_mine()).count(), 1);
+    /// ```
+    ///
+    /// [m_layer]: #method.layer "layer — returns the specified layer as a regular field"
+    #[inline(always)]
+    pub fn layers(&self) -> slice::Iter<'_, Field<Ct, Cf>> {
+        self.layers.iter()
+    }
+    /// Returns an iterator over mutable access to the tiles of every layer, starting from the top one. See [`layer_mut`][m_layer_mut] for the details.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{CubeField, TileState, Flag};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let mut field = CubeField::<(), ()>::empty([NonZeroUsize::new(3).unwrap(); 3]);
+    /// // Put a mine in the first column of every layer.
+    /// for mut layer in field.layers_mut() {
+    ///     for tile in layer.column_mut(0) {
+    ///         tile.state = TileState::Mine(Flag::NotFlagged);
+    ///     }
+    /// }
+    /// assert_eq!(field.all_tiles().filter(|tile| tile.state.is_mine()).count(), 9);
+    /// // The layers can also be read through the mutable access.
+    /// assert_eq!(field.layer_mut(2).as_field().mines().count(), 3);
+    /// ```
+    ///
+    /// [m_layer_mut]: #method.layer_mut "layer_mut — returns mutable access to the tiles of the specified layer"
+    #[inline]
+    pub fn layers_mut(&mut self) -> impl Iterator<Item = CubeLayerMut<'_, Ct, Cf>> {
+        self.layers.iter_mut().map(|field| CubeLayerMut {field})
+    }
+    /// Returns an iterator over all tiles of the field, layer by layer and in row-major order within every layer.
+    #[inline]
+    pub fn all_tiles(&self) -> impl Iterator<Item = &Tile<Ct, Cf>> {
+        self.layers.iter().flat_map(Field::all_tiles)
+    }
+    /// Returns an iterator over mutable references to all tiles of the field, layer by layer and in row-major order within every layer.
+    #[inline]
+    pub fn all_tiles_mut(&mut self) -> impl Iterator<Item = &mut Tile<Ct, Cf>> {
+        self.layers.iter_mut().flat_map(Field::all_tiles_mut)
+    }
+    /// Returns `true` if the field is fully solved (doesn't have any more safe tiles to open), `false` otherwise.
+    #[must_use = "traversing the entire field is obscenely expensive"]
+    pub fn solved(&self) -> bool {
+        self.layers.iter().all(Field::solved)
+    }
+    /// Returns the number of tiles which are not mines and are yet to be opened, which is also the number of tiles which are left to open to solve the field.
+    #[must_use = "traversing the entire field is obscenely expensive"]
+    pub fn tiles_to_open(&self) -> usize {
+        self.layers.iter().map(Field::tiles_to_open).sum()
+    }
+    /// Checks that the numbers on all open tiles match the amount of mines among their 26 neighbors. See [`Field::validate`][m_validate] for more.
+    ///
+    /// # Errors
+    /// Returns [`InconsistentNumber`][e_in] for the first tile which shows a wrong number.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{CubeField, TileState, InvalidFieldError};
+    /// # use core::num::{NonZeroUsize, NonZeroU8};
+    /// #
+    /// let mut field = CubeField::<(), ()>::empty([NonZeroUsize::new(2).unwrap(); 3]);
+    /// field[[1, 0, 1]].state = TileState::OpenNumber(NonZeroU8::new(1).unwrap());
+    /// assert_eq!(
+    ///     field.validate(),
+    ///     Err(InvalidFieldError::InconsistentNumber {location: [1, 0, 1], neighboring_mines: 0}),
+    /// );
+    /// ```
+    ///
+    /// [m_validate]: ../struct.Field.html#method.validate "validate — checks that the numbers on all open tiles match the amount of mines around them"
+    /// [e_in]: ../enum.InvalidFieldError.html#variant.InconsistentNumber "InvalidFieldError::InconsistentNumber — an open tile shows a different number than the amount of mines around it"
+    pub fn validate(&self) -> Result<(), InvalidFieldError<CubeCoordinates>> {
+        grid::validate(self)
+    }
+    /// Returns an iterator over the locations of the neighbors of the specified tile which are inside the field, in the order of [`CubeChordOutcome`][cco].
+    ///
+    /// [cco]: type.CubeChordOutcome.html "CubeChordOutcome — the outcome of a chord operation on a three-dimensional field"
+    #[inline(always)]
+    pub const fn neighbor_locations(&self, location: CubeCoordinates) -> CubeNeighborLocations {
+        CubeNeighborLocations::new(self.dimensions, location)
+    }
+    /// Counts the neighbors of the specified tile which are mines. A location outside the field has no neighbors.
+    #[must_use = "this is a lookup of up to 26 tiles"]
+    pub fn count_neighboring_mines(&self, location: CubeCoordinates) -> u8 {
+        grid::count_neighboring_mines(self, location)
+    }
+    /// Returns the outcome of clicking the specified tile **without affecting the field**, or `None` if the index is out of bounds.
+    pub fn peek(&self, coordinates: CubeCoordinates) -> Option<ClickOutcome> {
+        grid::peek(self, coordinates)
+    }
+    /// Opens **exactly one** tile and returns the outcome of clicking it, or `None` if the index is out of bounds. **Chords and clearings are not handled** and must be executed manually, usually with [`chord`][m_chord] and [`open_clearing`][m_open_clearing].
+    ///
+    /// [m_chord]: #method.chord "chord — performs a chord operation on the specified tile"
+    /// [m_open_clearing]: #method.open_clearing "open_clearing — fully opens the clearing around the specified tile"
+    pub fn open(&mut self, coordinates: CubeCoordinates) -> Option<ClickOutcome> {
+        grid::open(self, coordinates)
+    }
+    /// Performs a chord on the specified tile, opening all of its closed unflagged neighbors if the number on it matches the amount of flags around it.
+    ///
+    /// Just like with [`Field::chord`][m_chord], the returned array contains the outcome of opening every neighbor, and the clearings reported by them must be opened manually. Nothing happens if the chord can't be performed; use [`try_chord`][m_try_chord] to find out why.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{CubeField, TileState, Flag, ClickOutcome};
+    /// # use core::num::{NonZeroUsize, NonZeroU8};
+    /// #
+    /// let mut field = CubeField::<(), ()>::empty([NonZeroUsize::new(3).unwrap(); 3]);
+    /// field[[0, 0, 0]].state = TileState::Mine(Flag::Flagged);
+    /// field.open([1, 1, 1]);
+    /// assert_eq!(field[[1, 1, 1]].state, TileState::OpenNumber(NonZeroU8::new(1).unwrap()));
+    ///
+    /// let outcome = field.chord([1, 1, 1]);
+    /// // The flagged mine is the first neighbor, so it stays closed.
+    /// assert_eq!(outcome[0], ClickOutcome::Nothing);
+    /// assert_eq!(outcome[25], ClickOutcome::OpenClearing);
+    /// assert_eq!(outcome.iter().filter(|&&o| o == ClickOutcome::Nothing).count(), 1);
+    /// assert!(field.solved());
+    /// ```
+    ///
+    /// [m_chord]: ../struct.Field.html#method.chord "chord — performs a chord operation on the specified tile"
+    /// [m_try_chord]: #method.try_chord "try_chord — performs a chord on the specified tile, or returns an error if the chord can't be performed"
+    pub fn chord(&mut self, coordinates: CubeCoordinates) -> CubeChordOutcome {
+        self.try_chord(coordinates).unwrap_or_default()
+    }
+    /// Performs a chord on the specified tile, or returns an error if the chord can't be performed.
+    ///
+    /// # Errors
+    /// Returns [`OutOfBounds`][e_oob] if the location is outside the field, [`NotANumber`][e_nan] if the tile isn't an open number tile and [`FlagCountMismatch`][e_fcm] if the amount of flags around the tile doesn't match its number.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{CubeField, Error};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let mut field = CubeField::<(), ()>::empty([NonZeroUsize::new(2).unwrap(); 3]);
+    /// assert_eq!(field.try_chord([0, 1, 2]), Err(Error::OutOfBounds([0, 1, 2])));
+    /// assert_eq!(field.try_chord([0, 1, 1]), Err(Error::NotANumber([0, 1, 1])));
+    /// ```
+    ///
+    /// [e_oob]: ../enum.Error.html#variant.OutOfBounds "Error::OutOfBounds — the specified location is outside the field"
+    /// [e_nan]: ../enum.Error.html#variant.NotANumber "Error::NotANumber — a chord was performed on a tile which isn't an open number tile"
+    /// [e_fcm]: ../enum.Error.html#variant.FlagCountMismatch "Error::FlagCountMismatch — a chord was performed on a number tile which is surrounded by a different amount of flags than its number"
+    pub fn try_chord(&mut self, coordinates: CubeCoordinates) -> Result<CubeChordOutcome, Error<CubeCoordinates>> {
+        let mut result = [ClickOutcome::Nothing; NUM_NEIGHBORS];
+        grid::chord(self, coordinates, &mut result)?;
+        Ok(result)
+    }
+    /// Fully opens the clearing around the specified tile, optionally including the "shore" (tiles with numbers), or returns `None` if the location is out of bounds, contains a mine, is void or has 1 or more neighboring mines.
+    ///
+    /// The returned values are the same as the ones of [`ClearingMut::open`][m_open]: the amount of tiles which were opened and the total size of the clearing.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{CubeField, TileState, Flag};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let mut field = CubeField::<(), ()>::empty([NonZeroUsize::new(3).unwrap(); 3]);
+    /// field[[0, 0, 0]].state = TileState::Mine(Flag::NotFlagged);
+    /// // Numbers can't anchor a clearing.
+    /// assert_eq!(field.open_clearing([1, 1, 1], true), None);
+    /// // The mine has seven neighbors, so the remaining 19 tiles form the clearing.
+    /// assert_eq!(field.clone().open_clearing([2, 2, 2], false), Some((19, NonZeroUsize::new(19).unwrap())));
+    /// assert_eq!(field.open_clearing([2, 2, 2], true), Some((26, NonZeroUsize::new(26).unwrap())));
+    /// assert!(field.solved());
+    /// ```
+    ///
+    /// [m_open]: ../struct.ClearingMut.html#method.open "open — fully opens the clearing on the field"
+    pub fn open_clearing(&mut self, anchor_location: CubeCoordinates, include_shore: bool) -> Option<(usize, NonZeroUsize)> {
+        grid::open_clearing(self, anchor_location, include_shore, false)
+    }
+    /// Calculates the 3BV value of the field, using the same rules as [`Field::calculate_3bv`][m_3bv] with three-dimensional clearings.
+    ///
+    /// Since the field is modified in an undefined way in the process, it is taken by value.
+    ///
+    /// [m_3bv]: ../struct.Field.html#method.calculate_3bv "calculate_3bv — calculates the 3BV value of the field"
+    #[must_use = "calculating the 3BV value for any possible field requires traversing the entire field two times and opening clearings"]
+    pub fn calculate_3bv(mut self) -> usize {
+        grid::calculate_3bv(&mut self)
+    }
+}
+#[cfg(feature = "generation")]
+impl<Ct, Cf> CubeField<Ct, Cf> {
+    /// Adds exactly the specified amount of mines while keeping the specified [safe zone][sz] free of mines, using the thread-local random number generator. A `Neighborhood` safe zone covers the 26 neighbors of the tile.
+    ///
+    /// # Errors
+    /// See [`populate_exact_with_rng`][m_pewr].
+    ///
+    /// [sz]: ../enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+    /// [m_pewr]: #method.populate_exact_with_rng "populate_exact_with_rng — adds exactly the specified amount of mines using the specified random number generator"
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn populate_exact<'a>(&mut self, num_mines: usize, safe_zone: impl Into<SafeZone<'a, CubeCoordinates>>) -> Result<(), GenerationError<CubeCoordinates>> {
+        self.populate_exact_with_rng(num_mines, safe_zone, &mut rand::thread_rng())
+    }
+    /// Adds exactly the specified amount of mines while keeping the specified [safe zone][sz] free of mines, using the specified random number generator. A `Neighborhood` safe zone covers the 26 neighbors of the tile.
+    ///
+    /// Just like [`Field::populate_exact_with_rng`][m_field_pewr], mines are only placed on tiles which don't already contain one and aren't void, and only `u64` values are sampled from the generator.
+    ///
+    /// # Errors
+    /// Returns [`TooManyMines`][e_tmm] if there are less than `num_mines` tiles which can hold a new mine and [`SafeSpotOutOfBounds`][e_ssoob] if the safe zone includes a tile outside the field. The field is not modified in both cases.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{CubeField, GenerationError, SafeZone, ClickOutcome};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let mut field = CubeField::<(), ()>::empty([NonZeroUsize::new(5).unwrap(); 3]);
+    /// field.populate_exact_from_seed(20, SafeZone::Neighborhood([2, 2, 2]), 1337)?;
+    /// assert_eq!(field.all_tiles().filter(|t| t.state.is_mine()).count(), 20);
+    /// assert_eq!(field.open([2, 2, 2]), Some(ClickOutcome::OpenClearing));
+    ///
+    /// let mut tiny = CubeField::<(), ()>::empty([NonZeroUsize::new(2).unwrap(); 3]);
+    /// assert_eq!(
+    ///     tiny.populate_exact_from_seed(1, SafeZone::Neighborhood([0, 0, 0]), 1337),
+    ///     Err(GenerationError::TooManyMines {requested: 1, available: 0}),
+    /// );
+    /// assert_eq!(
+    ///     tiny.populate_exact_from_seed(1, Some([0, 0, 2]), 1337),
+    ///     Err(GenerationError::SafeSpotOutOfBounds([0, 0, 2])),
+    /// );
+    /// # Ok::<(), GenerationError<[usize; 3]>>(())
+    /// ```
+    ///
+    /// [sz]: ../enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+    /// [m_field_pewr]: ../struct.Field.html#method.populate_exact_with_rng "populate_exact_with_rng — adds exactly the specified amount of mines using the specified random number generator"
+    /// [e_tmm]: ../enum.GenerationError.html#variant.TooManyMines "TooManyMines — the requested amount of mines does not fit on the field"
+    /// [e_ssoob]: ../enum.GenerationError.html#variant.SafeSpotOutOfBounds "SafeSpotOutOfBounds — the safe zone includes a tile outside the field"
+    pub fn populate_exact_with_rng<'a, R>(&mut self, num_mines: usize, safe_zone: impl Into<SafeZone<'a, CubeCoordinates>>, rng: &mut R) -> Result<(), GenerationError<CubeCoordinates>>
+    where R: Rng + ?Sized {
+        grid::populate_exact(self, num_mines, safe_zone.into(), rng)
+    }
+    /// Adds exactly the specified amount of mines while keeping the specified [safe zone][sz] free of mines. The mine layout is fully determined by the seed, with the same guarantees as the ones of [`Field::populate_exact_from_seed`][m_pefs].
+    ///
+    /// # Errors
+    /// See [`populate_exact_with_rng`][m_pewr].
+    ///
+    /// [sz]: ../enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+    /// [m_pefs]: ../struct.Field.html#method.populate_exact_from_seed "populate_exact_from_seed — adds exactly the specified amount of mines using a random number generator with the specified seed"
+    /// [m_pewr]: #method.populate_exact_with_rng "populate_exact_with_rng — adds exactly the specified amount of mines using the specified random number generator"
+    #[inline]
+    pub fn populate_exact_from_seed<'a>(&mut self, num_mines: usize, safe_zone: impl Into<SafeZone<'a, CubeCoordinates>>, seed: u64) -> Result<(), GenerationError<CubeCoordinates>> {
+        self.populate_exact_with_rng(num_mines, safe_zone, &mut Pcg64::seed_from_u64(seed))
+    }
+}
+impl<Ct, Cf> Grid for CubeField<Ct, Cf> {
+    type Location = CubeCoordinates;
+    type Payload = Ct;
+    type Flag = Cf;
+    #[inline(always)]
+    fn area(&self) -> usize {
+        self.layers.len() * self.layers[0].all_tiles().len()
+    }
+    /// Returns the index of the specified tile when all tiles are listed layer by layer, in row-major order within every layer.
+    #[inline(always)]
+    fn index_of(&self, location: CubeCoordinates) -> usize {
+        let [width, height] = [self.dimensions[0].get(), self.dimensions[1].get()];
+        location[0] + (location[1] + location[2] * height) * width
+    }
+    /// Returns the location of the tile with the specified index in the order of [`all_tiles`][m_all_tiles].
+    ///
+    /// [m_all_tiles]: #method.all_tiles "all_tiles — returns an iterator over all tiles of the field"
+    #[inline(always)]
+    fn location_of(&self, index: usize) -> CubeCoordinates {
+        let [width, height] = [self.dimensions[0].get(), self.dimensions[1].get()];
+        [index % width, index / width % height, index / (width * height)]
+    }
+    #[inline(always)]
+    fn neighbor_count(&self) -> usize {
+        NUM_NEIGHBORS
+    }
+    #[inline(always)]
+    fn neighbor(&self, center: CubeCoordinates, direction: usize) -> Option<CubeCoordinates> {
+        cube_neighbor(self.dimensions, center, direction)
+    }
+    #[inline(always)]
+    fn tile(&self, location: CubeCoordinates) -> Option<&Tile<Ct, Cf>> {
+        self.get(location)
+    }
+    #[inline]
+    fn update_state<F>(&mut self, location: CubeCoordinates, update: F)
+    where F: FnOnce(TileState<Cf>) -> TileState<Cf> {
+        if let Some(layer) = self.layers.get_mut(location[2]) {
+            layer.update_state([location[0], location[1]], update);
+        }
+    }
+}
+
+/// Mutable access to the tiles of one layer of a [`CubeField`][cf], returned by [`layer_mut`][m_layer_mut] and [`layers_mut`][m_layers_mut].
+///
+/// Unlike a mutable reference to the underlying [`Field`][field], this doesn't allow replacing the layer or changing its settings, which would make it disagree with the rest of the three-dimensional field.
+///
+/// [cf]: struct.CubeField.html "CubeField — a three-dimensional playfield"
+/// [m_layer_mut]: struct.CubeField.html#method.layer_mut "layer_mut — returns mutable access to the tiles of the specified layer"
+/// [m_layers_mut]: struct.CubeField.html#method.layers_mut "layers_mut — returns an iterator over mutable access to the tiles of every layer"
+/// [field]: ../struct.Field.html "Field — represents a playfield"
+#[derive(Debug)]
+pub struct CubeLayerMut<'f, Ct, Cf> {
+    field: &'f mut Field<Ct, Cf>,
+}
+impl<Ct, Cf> CubeLayerMut<'_, Ct, Cf> {
+    /// Returns the layer as a regular field, for reading. See [`CubeField::layer`][m_layer] for the caveats.
+    ///
+    /// [m_layer]: struct.CubeField.html#method.layer "layer — returns the specified layer as a regular field"
+    #[inline(always)]
+    pub const fn as_field(&self) -> &Field<Ct, Cf> {
+        self.field
+    }
+    /// Returns a mutable reference to the tile at the specified location on the layer, or `None` if the location is out of bounds.
+    #[inline(always)]
+    pub fn get_mut(&mut self, coordinates: FieldCoordinates) -> Option<&mut Tile<Ct, Cf>> {
+        self.field.get_mut(coordinates)
+    }
+    /// Returns a mutable iterator over a single row of the layer.
+    ///
+    /// # Panics
+    /// Panics if the specified row is out of range.
+    #[inline(always)]
+    #[cfg_attr(feature = "track_caller", track_caller)]
+    pub fn row_mut(&mut self, row: usize) -> RowIterMut<'_, Ct, Cf> {
+        self.field.row_mut(row)
+    }
+    /// Returns a mutable iterator over a single column of the layer.
+    ///
+    /// # Panics
+    /// Panics if the specified column is out of range.
+    #[inline(always)]
+    #[cfg_attr(feature = "track_caller", track_caller)]
+    pub fn column_mut(&mut self, column: usize) -> ColumnIterMut<'_, Ct, Cf> {
+        self.field.column_mut(column)
+    }
+    /// Returns an iterator over the rows of the layer, each of which is a mutable iterator over its tiles.
+    #[inline(always)]
+    pub fn rows_mut(&mut self) -> FieldRowsIterMut<'_, Ct, Cf> {
+        self.field.rows_mut()
+    }
+    /// Returns an iterator over the columns of the layer, each of which is a mutable iterator over its tiles. All of the columns can be borrowed at the same time.
+    #[inline(always)]
+    pub fn columns_mut(&mut self) -> FieldColumnsIterMut<'_, Ct, Cf> {
+        self.field.columns_mut()
+    }
+    /// Returns a mutable iterator over all tiles of the layer, in row-major order.
+    #[inline(always)]
+    pub fn all_tiles_mut(&mut self) -> slice::IterMut<'_, Tile<Ct, Cf>> {
+        self.field.all_tiles_mut()
+    }
+    /// Returns a mutable iterator over all tiles of the layer along with their locations on it, in row-major order.
+    #[inline(always)]
+    pub fn enumerate_tiles_mut(&mut self) -> EnumerateTilesMut<'_, Ct, Cf> {
+        self.field.enumerate_tiles_mut()
+    }
+}
+impl<Ct, Cf> Index<FieldCoordinates> for CubeLayerMut<'_, Ct, Cf> {
+    type Output = Tile<Ct, Cf>;
+    /// Returns the tile at the column `index[0]` and row `index[1]` of the layer.
+    ///
+    /// # Panics
+    /// Index checking is enabled for this method.
+    #[inline(always)]
+    fn index(&self, coordinates: FieldCoordinates) -> &Self::Output {
+        &self.field[coordinates]
+    }
+}
+impl<Ct, Cf> IndexMut<FieldCoordinates> for CubeLayerMut<'_, Ct, Cf> {
+    /// Returns the tile at the column `index[0]` and row `index[1]` of the layer.
+    ///
+    /// # Panics
+    /// Index checking is enabled for this method. For a version which returns an `Option` instead of panicking if the index is out of bounds, see `get_mut`.
+    #[inline(always)]
+    fn index_mut(&mut self, coordinates: FieldCoordinates) -> &mut Self::Output {
+        &mut self.field[coordinates]
+    }
+}
+
+impl<Ct, Cf> Index<CubeCoordinates> for CubeField<Ct, Cf> {
+    type Output = Tile<Ct, Cf>;
+    /// Returns the tile at the column `index[0]`, row `index[1]` and layer `index[2]`, all starting at zero.
+    ///
+    /// # Panics
+    /// Index checking is enabled for this method. For a version which returns an `Option` instead of panicking if the index is out of bounds, see `get`.
+    #[inline(always)]
+    fn index(&self, coordinates: CubeCoordinates) -> &Self::Output {
+        self.get(coordinates).expect("index out of bounds")
+    }
+}
+impl<Ct, Cf> IndexMut<CubeCoordinates> for CubeField<Ct, Cf> {
+    /// Returns the tile at the column `index[0]`, row `index[1]` and layer `index[2]`, all starting at zero.
+    ///
+    /// # Panics
+    /// Index checking is enabled for this method. For a version which returns an `Option` instead of panicking if the index is out of bounds, see `get_mut`.
+    #[inline(always)]
+    fn index_mut(&mut self, coordinates: CubeCoordinates) -> &mut Self::Output {
+        self.get_mut(coordinates).expect("index out of bounds")
+    }
+}
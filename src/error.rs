@@ -0,0 +1,92 @@
+//! The error type for field operations.
+
+use core::{
+    fmt::{self, Formatter},
+    num::NonZeroUsize,
+};
+use crate::FieldCoordinates;
+
+/// The error type for the fallible `try_*` versions of field operations, describing why an operation couldn't be performed.
+///
+/// The infallible versions of these operations return `None` or silently do nothing in these cases, which is enough for the game logic, but doesn't allow the frontend to tell the player why a click had no effect.
+///
+/// The type parameter is the type of the locations reported by the error, which defaults to the coordinates on a regular or hexagonal field. Errors from a [`CubeField`][cube] report [`CubeCoordinates`][cc] instead.
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Field, TileState, Flag, Error};
+/// # use core::num::{NonZeroUsize, NonZeroU8};
+/// #
+/// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(3).unwrap(); 2]);
+/// field[[0, 0]].state = TileState::Mine(Flag::NotFlagged);
+/// field[[1, 1]].state = TileState::OpenNumber(NonZeroU8::new(1).unwrap());
+///
+/// assert_eq!(field.try_open([3, 0]), Err(Error::OutOfBounds([3, 0])));
+/// assert_eq!(field.try_open([1, 1]), Err(Error::AlreadyOpen([1, 1])));
+/// assert_eq!(field.try_chord([2, 2]), Err(Error::NotANumber([2, 2])));
+/// assert_eq!(
+///     field.try_chord([1, 1]),
+///     Err(Error::FlagCountMismatch {location: [1, 1], flags: 0, mines: 1}),
+/// );
+/// ```
+///
+/// [cube]: cube/struct.CubeField.html "CubeField — a three-dimensional playfield"
+/// [cc]: cube/type.CubeCoordinates.html "CubeCoordinates — the coordinates of a tile on a three-dimensional field"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Error<L = FieldCoordinates> {
+    /// The specified location is outside the field.
+    OutOfBounds(L),
+    /// The tile at the specified location is already open, so opening it does nothing.
+    AlreadyOpen(L),
+    /// A chord was performed on a tile which isn't an open number tile.
+    NotANumber(L),
+    /// A chord was performed on a number tile which is surrounded by a different amount of flags than its number.
+    FlagCountMismatch {
+        /// The location of the number tile.
+        location: L,
+        /// The amount of flags around the tile.
+        flags: u8,
+        /// The number on the tile.
+        mines: u8,
+    },
+    /// The requested amount of mines does not fit on the field.
+    TooManyMines {
+        /// The amount of mines which was requested.
+        requested: usize,
+        /// The amount of tiles where a mine could be placed.
+        available: usize,
+    },
+    /// The percentage of mines is not a positive number.
+    InvalidMinePercentage,
+    /// None of the generated fields satisfied the requirements within the specified amount of attempts.
+    AttemptsExhausted(NonZeroUsize),
+}
+impl<L: fmt::Debug> fmt::Display for Error<L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds(location) => write!(f,
+                "the location {location:?} is outside the field"
+            ),
+            Self::AlreadyOpen(location) => write!(f,
+                "the tile at {location:?} is already open"
+            ),
+            Self::NotANumber(location) => write!(f,
+                "the tile at {location:?} is not an open number tile"
+            ),
+            Self::FlagCountMismatch {location, flags, mines} => write!(f,
+                "the tile at {location:?} shows {mines} mines, but is surrounded by {flags} flags"
+            ),
+            Self::TooManyMines {requested, available} => write!(f,
+                "requested {requested} mines, but only {available} tiles are available"
+            ),
+            Self::InvalidMinePercentage => write!(f,
+                "the percentage of mines is not a positive number"
+            ),
+            Self::AttemptsExhausted(attempts) => write!(f,
+                "no suitable field was generated in {attempts} attempts"
+            ),
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl<L: fmt::Debug> std::error::Error for Error<L> {}
@@ -4,13 +4,12 @@
 
 use core::{
     ops::{Index, IndexMut},
-    num::{NonZeroUsize, NonZeroU8},
+    num::NonZeroUsize,
+    mem,
 };
+use core::fmt::{self, Formatter};
 #[cfg(feature = "serialization")]
-use core::{
-    fmt::{self, Formatter},
-    marker::PhantomData,
-};
+use core::marker::PhantomData;
 use alloc::{
     vec::Vec
 };
@@ -21,10 +20,15 @@ use serde::{
     de::{Deserializer, Visitor, MapAccess, SeqAccess}
 };
 use crate::{
-    Tile, TileState, Flag, ClickOutcome,
+    Tile, TileState, ClickOutcome, Error,
     Clearing, ClearingMut,
-    RowIter, ColumnIter,
-    FieldRowsIter, FieldColumnsIter
+    RowIter, ColumnIter, RowIterMut, ColumnIterMut,
+    EnumerateTiles, EnumerateTilesMut, FilteredTiles,
+    FieldRowsIter, FieldColumnsIter, FieldRowsIterMut, FieldColumnsIterMut,
+    NeighborLocations, Neighbors, NeighborsMut,
+    Topology, Neighborhood,
+    iter::neighbor,
+    grid::{self, Grid},
 };
 
 /// Represents a playfield.
@@ -39,6 +43,8 @@ use crate::{
 pub struct Field<Ct, Cf> {
     dimensions: FieldDimensions,
     storage: Vec<Tile<Ct, Cf>>,
+    topology: Topology,
+    neighborhood: Neighborhood,
 }
 /// A field without any custom tile data or custom flags, typically used for serialization of fields without custom flags.
 pub type SimpleField = Field<(), ()>;
@@ -52,16 +58,32 @@ pub type FieldDimensions = [NonZeroUsize; 2];
 pub type FieldCoordinates = [usize; 2];
 /// The outcome of a [chord operation][m_chord].
 ///
-/// The entries are the adjacent & diagonal tiles in clockwise order, starting from top-left: ↖, ↑, ↗, →, ↘, ↓, ↙, ←.
+/// There is one entry for every offset of the [neighborhood][neighborhood] of the field, in its order. For the default Moore neighborhood, the entries are the adjacent & diagonal tiles in clockwise order, starting from top-left: ↖, ↑, ↗, →, ↘, ↓, ↙, ←.
 ///
 /// [m_chord]: struct.Field.html#method.chord "chord — performs a chord operation on the specified tile"
-pub type ChordOutcome = [ClickOutcome; 8];
+/// [neighborhood]: struct.Neighborhood.html "Neighborhood — describes which tiles around a tile count as its neighbors"
+pub type ChordOutcome = Vec<ClickOutcome>;
 /// The outcome of one of the chords in a [recursive chord operation][m_rechord].
 ///
 /// The `Chord` variant of `ClickOutcome` does **not** require any processing — all chords reported by these have already been executed by the time the function finishes execution.
 ///
 /// [m_rechord]: struct.Field.html#method.recursive_chord "recursive_chord — performs a chord operation on the specified tile recursively, i.e. runs chords for all number tiles which were uncovered from chording"
 pub type RecursiveChordOutcome = (FieldCoordinates, ChordOutcome);
+/// The outcome of the [first click][m_of] of a game.
+///
+/// [m_of]: struct.Field.html#method.open_first "open_first — opens the specified tile as the first click of a game, relocating the mine on it if there is one"
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct FirstClickOutcome {
+    /// The outcome of clicking the tile after the mine, if any, was relocated.
+    ///
+    /// This is only `Explosion` if the field is completely filled with mines, since there's nowhere to relocate the mine to in that case.
+    pub outcome: ClickOutcome,
+    /// The new location of the mine which was on the clicked tile, or `None` if there was no mine there or it could not be relocated.
+    ///
+    /// Frontends which draw the field before the first click don't need to do anything special about this, since the mine was on a closed tile before and is on a closed tile after the relocation.
+    pub relocated_mine: Option<FieldCoordinates>,
+}
 impl<Ct, Cf> Field<Ct, Cf>
 where Ct: Default {
     /// Creates an empty field filled with unopened tiles, with the given dimensions.
@@ -71,7 +93,9 @@ where Ct: Default {
         let (width, height) = (dimensions[0].get(), dimensions[1].get());
         let mut tfield = Self {
             storage: Vec::with_capacity(width * height),
-            dimensions
+            dimensions,
+            topology: Topology::Bounded,
+            neighborhood: Neighborhood::MOORE,
         };
         for _ in 0..(width * height) {
             tfield.storage.push(Tile::default());
@@ -85,7 +109,7 @@ impl<Ct, Cf> Field<Ct, Cf> {
     /// This is primarily useful when the custom tile data stores runtime-specific information, like the corresponding entities in an entity-component-system architecture or such.
     #[allow(clippy::use_self)]
     pub fn remove_tile_payload(self) -> Field<(), Cf> {
-        let dimensions = self.dimensions;
+        let (dimensions, topology, neighborhood) = (self.dimensions, self.topology, self.neighborhood);
         let area = dimensions[0].get() * dimensions[1].get();
         let mut resulting_storage = Vec::with_capacity(area);
         for tile in self.into_tiles() {
@@ -95,9 +119,11 @@ impl<Ct, Cf> Field<Ct, Cf> {
         }
         Field::<(), Cf>::from_dimensions_and_storage(dimensions, resulting_storage)
             .unwrap_or_else(|| unreachable!("the length of the backing storage should've matched the area but it didn't, this shouldn't be possible"))
+            .with_topology(topology)
+            .with_neighborhood(neighborhood)
     }
 
-    /// Creates a field with the specified dimensions from the specified `Vec` of tiles, given in [row-major order][rmo].
+    /// Creates a bounded field with the Moore neighborhood and the specified dimensions from the specified `Vec` of tiles, given in [row-major order][rmo].
     ///
     /// Keep in mind that indexing over fields is still done in column-major order.
     ///
@@ -106,55 +132,143 @@ impl<Ct, Cf> Field<Ct, Cf> {
     pub fn from_dimensions_and_storage(dimensions: FieldDimensions, storage: Vec<Tile<Ct, Cf>>) -> Option<Self> {
         let area = dimensions[0].get() * dimensions[1].get();
         if storage.len() == area {
-            Some(Self {dimensions, storage})
+            Some(Self {dimensions, storage, topology: Topology::Bounded, neighborhood: Neighborhood::MOORE})
         } else {
             None
         }
     }
-    /// Adds mines with the selected percentage of mines and, optionally, a safe spot, which can never have any surrounding mines.
-    #[cfg(feature = "generation")]
-    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
-    pub fn populate(&mut self, mine_percentage: f64, safe_spot: Option<FieldCoordinates>) {
-        use rand::Rng;
-        assert!(mine_percentage > 0.0); // no
-        let mut rng = rand::thread_rng();
-
-        let (width, height) = (self.dimensions[0].get(), self.dimensions[1].get());
-
-        let area = width * height;
-        let num_mines: usize = (area as f64 * mine_percentage).round() as usize; // The number of mines is usize because the area is usize.
-        if safe_spot.is_some() {
-            assert!(area > num_mines);
-        } else {
-            assert!(area >= num_mines);
-        }
-
-        // We're using loop+counter instead of a range because we don't want to just discard a mine if it collides with the safe spot. Instead, we're going to
-        // skip over the decrement and retry. This might freeze the game if the RNG chooses to hit the safe spot multiple times, but that's so unlikely that
-        // we're going to disregard that for the sake of this example.
-        let mut mines_left = num_mines;
-        loop {
-            let rnum: usize = rng.gen_range(0, area);
-            let mine_location = [rnum % width, rnum / width];
-            if let Some(spot) = safe_spot {
-                if mine_location == spot {
-                    continue; // Jumps over the decrement.
-                }
-            }
-            self[mine_location].state = TileState::Mine(Flag::NotFlagged); // Install the mine.
-            if mines_left == 0 {break};
-            mines_left -= 1; // Implicit else, decrements otherwise.
-        }
+    /// Checks that the numbers on all open tiles match the amount of mines around them, which is always the case for fields played by the rules but not necessarily for fields loaded from untrusted sources or edited by hand.
+    ///
+    /// Fields are validated automatically when they are deserialized.
+    ///
+    /// # Errors
+    /// Returns [`InconsistentNumber`][e_in] for the first open tile, in row-major order, which shows a different number than the amount of mines around it. `OpenEmpty` tiles count as showing zero.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{Field, TileState, Flag, InvalidFieldError};
+    /// # use core::num::{NonZeroUsize, NonZeroU8};
+    /// #
+    /// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(1).unwrap()]);
+    /// field[[0, 0]].state = TileState::Mine(Flag::NotFlagged);
+    /// field[[1, 0]].state = TileState::OpenNumber(NonZeroU8::new(1).unwrap());
+    /// assert_eq!(field.validate(), Ok(()));
+    ///
+    /// field[[2, 0]].state = TileState::OpenNumber(NonZeroU8::new(2).unwrap());
+    /// assert_eq!(
+    ///     field.validate(),
+    ///     Err(InvalidFieldError::InconsistentNumber {location: [2, 0], neighboring_mines: 0}),
+    /// );
+    /// ```
+    ///
+    /// [e_in]: enum.InvalidFieldError.html#variant.InconsistentNumber "InvalidFieldError::InconsistentNumber — an open tile shows a different number than the amount of mines around it"
+    pub fn validate(&self) -> Result<(), InvalidFieldError> {
+        grid::validate(self)
     }
     /// Returns the width and height of the field.
     #[inline(always)]
     pub const fn dimensions(&self) -> FieldDimensions {
         self.dimensions
     }
+    /// Returns the [topology][topology] of the field, which is `Bounded` unless specified otherwise with [`with_topology`][m_with_topology].
+    ///
+    /// [topology]: enum.Topology.html "Topology — describes which edges of a field touch each other"
+    /// [m_with_topology]: #method.with_topology "with_topology — changes the topology of the field"
+    #[inline(always)]
+    pub const fn topology(&self) -> Topology {
+        self.topology
+    }
+    /// Changes the [topology][topology] of the field, i.e. which of its edges touch each other.
+    ///
+    /// The tiles are kept as they are, which means that the numbers on the open tiles can become wrong, so this is meant to be used on fields which weren't opened yet.
+    ///
+    /// [topology]: enum.Topology.html "Topology — describes which edges of a field touch each other"
+    #[inline(always)]
+    #[must_use = "the topology is changed on the returned field"]
+    pub const fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+    /// Returns the [neighborhood][neighborhood] of the field, which is the Moore neighborhood unless specified otherwise with [`with_neighborhood`][m_with_neighborhood].
+    ///
+    /// [neighborhood]: struct.Neighborhood.html "Neighborhood — describes which tiles around a tile count as its neighbors"
+    /// [m_with_neighborhood]: #method.with_neighborhood "with_neighborhood — changes the neighborhood of the field"
+    #[inline(always)]
+    pub const fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+    /// Changes the [neighborhood][neighborhood] of the field, i.e. which tiles around a tile count as its neighbors.
+    ///
+    /// Just like with [`with_topology`][m_with_topology], the tiles are kept as they are, so this is meant to be used on fields which weren't opened yet.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{Field, TileState, Flag, Neighborhood};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(3).unwrap(); 2])
+    ///     .with_neighborhood(Neighborhood::VON_NEUMANN);
+    /// field[[0, 0]].state = TileState::Mine(Flag::NotFlagged);
+    /// // The diagonal neighbor of the mine doesn't count it, so the clearing reaches it.
+    /// let (opened, _) = field.clearing_mut([2, 2]).unwrap().open(true);
+    /// assert_eq!(opened, 8);
+    /// assert_eq!(field.calculate_3bv(), 1);
+    /// ```
+    ///
+    /// [neighborhood]: struct.Neighborhood.html "Neighborhood — describes which tiles around a tile count as its neighbors"
+    /// [m_with_topology]: #method.with_topology "with_topology — changes the topology of the field"
+    #[inline(always)]
+    #[must_use = "the neighborhood is changed on the returned field"]
+    pub const fn with_neighborhood(mut self, neighborhood: Neighborhood) -> Self {
+        self.neighborhood = neighborhood;
+        self
+    }
+    /// Turns every tile for which the closure returns `true` into a [void][void] one, which gives the field an irregular shape, and returns the modified field.
+    ///
+    /// Void tiles never hold mines, aren't counted as anyone's neighbors, stop clearings from spreading through them, can't be opened or flagged and are left out of [`tiles_to_open`][m_tiles_to_open] and [`count_closed_tiles`][m_count_closed_tiles]. All storage formats preserve them. Whatever the tiles held before is discarded, so this is meant to be called on an empty field before it's populated.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{Field, ClickOutcome};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// // A 5x3 field with a wall of void tiles down the middle.
+    /// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(5).unwrap(), NonZeroUsize::new(3).unwrap()])
+    ///     .with_voids(|[x, _]| x == 2);
+    /// assert_eq!(field.tiles_to_open(), 12);
+    /// assert_eq!(field.count_closed_tiles(), 12);
+    /// assert_eq!(field.open([2, 1]), Some(ClickOutcome::Nothing));
+    ///
+    /// // The clearing on the left doesn't spill over the wall.
+    /// let (opened, _) = field.clearing_mut([0, 1]).unwrap().open(true);
+    /// assert_eq!(opened, 6);
+    /// assert!(field[[3, 1]].state.is_closed());
+    /// # #[cfg(feature = "generation")] {
+    /// let mut holed = Field::<(), ()>::empty([NonZeroUsize::new(4).unwrap(); 2])
+    ///     .with_voids(|[x, y]| (1..3).contains(&x) && (1..3).contains(&y));
+    /// holed.populate_exact_from_seed(12, None, 42).unwrap();
+    /// assert!(holed.all_tiles().all(|tile| !(tile.state.is_void() && tile.state.is_mine())));
+    /// assert!(holed.solved());
+    /// # }
+    /// ```
+    ///
+    /// [void]: enum.TileState.html#variant.Void "TileState::Void — a hole in the field"
+    /// [m_tiles_to_open]: #method.tiles_to_open "tiles_to_open — returns the amount of tiles which the player needs to open in order to win the game"
+    /// [m_count_closed_tiles]: #method.count_closed_tiles "count_closed_tiles — returns the amount of tiles which have not been opened yet"
+    #[must_use = "the void tiles are set on the returned field"]
+    pub fn with_voids<F>(mut self, mut is_void: F) -> Self
+    where F: FnMut(FieldCoordinates) -> bool {
+        for (location, tile) in self.enumerate_tiles_mut() {
+            if is_void(location) {
+                tile.state = TileState::Void;
+            }
+        }
+        self
+    }
     /// Returns `true` if the field is fully solved (game win condition), `false` otherwise.
     #[must_use = "traversing the entire field is obscenely expensive"]
     pub fn solved(&self) -> bool {
-        self.tiles_to_open() > 0
+        self.tiles_to_open() == 0
     }
     /// Returns the amount of tiles which have been already opened.
     #[must_use = "traversing the entire field is obscenely expensive"]
@@ -196,35 +310,97 @@ impl<Ct, Cf> Field<Ct, Cf> {
     }
     /// Counts all neigboring mines around a spot.
     ///
-    /// All directly and diagonally adjacent mines are considered neighboring. If the tile is a mine, the tile itself isn't counted.
-    #[must_use = "this is a rather complex lookup with 16 branch points"]
+    /// The mines are counted in the [neighborhood][neighborhood] of the field, which by default consists of the directly and diagonally adjacent tiles. If the tile is a mine, the tile itself isn't counted. Neighbors outside the field are ignored, and a location outside the field has no neighboring mines.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{Field, TileState, Flag};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// // Check every mine layout of every field up to 3x3, which covers all corners and borders.
+    /// for width in 1..=3 {
+    ///     for height in 1..=3 {
+    ///         let area = width * height;
+    ///         for layout in 0..1_u32 << area {
+    ///             let is_mine = |x: isize, y: isize| {
+    ///                 x >= 0 && y >= 0 && x < width as isize && y < height as isize
+    ///                     && layout & 1 << (y as usize * width + x as usize) != 0
+    ///             };
+    ///             let mut field = Field::<(), ()>::empty([NonZeroUsize::new(width).unwrap(), NonZeroUsize::new(height).unwrap()]);
+    ///             for i in 0..area {
+    ///                 if layout & 1 << i != 0 {
+    ///                     field[[i % width, i / width]].state = TileState::Mine(Flag::NotFlagged);
+    ///                 }
+    ///             }
+    ///             for y in 0..height as isize {
+    ///                 for x in 0..width as isize {
+    ///                     let mut expected = 0;
+    ///                     for dy in -1..=1 {
+    ///                         for dx in -1..=1 {
+    ///                             if (dx, dy) != (0, 0) && is_mine(x + dx, y + dy) {expected += 1}
+    ///                         }
+    ///                     }
+    ///                     assert_eq!(field.count_neighboring_mines([x as usize, y as usize]), expected);
+    ///                 }
+    ///             }
+    ///             assert_eq!(field.count_neighboring_mines([width, height]), 0);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [neighborhood]: struct.Neighborhood.html "Neighborhood — describes which tiles around a tile count as its neighbors"
+    #[must_use = "this is a lookup of up to 48 tiles"]
     pub fn count_neighboring_mines(&self, location: FieldCoordinates) -> u8 {
-        let mut count = 0_u8;
-        if let Some(b) = self.is_mine([location[0] - 1, location[1] + 1]) {if b {count += 1;}};
-        if let Some(b) = self.is_mine([location[0]    , location[1] + 1]) {if b {count += 1;}};
-        if let Some(b) = self.is_mine([location[0] + 1, location[1] + 1]) {if b {count += 1;}};
-
-        if let Some(b) = self.is_mine([location[0] - 1, location[1]    ]) {if b {count += 1;}};
-        // Skip center
-        if let Some(b) = self.is_mine([location[0] + 1, location[1]    ]) {if b {count += 1;}};
-
-        if let Some(b) = self.is_mine([location[0] - 1, location[1] - 1]) {if b {count += 1;}};
-        if let Some(b) = self.is_mine([location[0]    , location[1] - 1]) {if b {count += 1;}};
-        if let Some(b) = self.is_mine([location[0] + 1, location[1] - 1]) {if b {count += 1;}};
-        count
+        grid::count_neighboring_mines(self, location)
+    }
+    /// Returns an iterator over the locations of the neighbors of the specified tile which are inside the field, according to the [neighborhood][neighborhood] of the field and in the order of [`ChordOutcome`][chord_outcome]. The edges which touch each other according to the [topology][topology] of the field are taken into account.
+    ///
+    /// The iterator doesn't borrow the field, so it can be used to modify the neighbors. See [`NeighborLocations`][nl] for more.
+    ///
+    /// [chord_outcome]: type.ChordOutcome.html "ChordOutcome — the outcome of a chord operation"
+    /// [neighborhood]: struct.Neighborhood.html "Neighborhood — describes which tiles around a tile count as its neighbors"
+    /// [topology]: enum.Topology.html "Topology — describes which edges of a field touch each other"
+    /// [nl]: iter/struct.NeighborLocations.html "NeighborLocations — iterates over the locations of the neighbors of a tile"
+    #[inline(always)]
+    pub const fn neighbor_locations(&self, location: FieldCoordinates) -> NeighborLocations {
+        NeighborLocations::new(self.dimensions, location)
+            .with_topology(self.topology)
+            .with_neighborhood(self.neighborhood)
+    }
+    /// Returns an iterator over the neighbors of the specified tile along with their locations, in the order of [`ChordOutcome`][chord_outcome]. Neighbors outside the field are skipped, and a location outside the field has no neighbors.
+    ///
+    /// [chord_outcome]: type.ChordOutcome.html "ChordOutcome — the outcome of a chord operation"
+    #[inline(always)]
+    pub const fn neighbors(&self, location: FieldCoordinates) -> Neighbors<'_, Ct, Cf> {
+        Neighbors::new(self, location)
+    }
+    /// Returns an iterator over mutable references to the neighbors of the specified tile along with their locations, in the order of [`ChordOutcome`][chord_outcome]. Neighbors outside the field are skipped, and a location outside the field has no neighbors.
+    ///
+    /// [chord_outcome]: type.ChordOutcome.html "ChordOutcome — the outcome of a chord operation"
+    #[inline(always)]
+    pub fn neighbors_mut(&mut self, location: FieldCoordinates) -> NeighborsMut<'_, Ct, Cf> {
+        NeighborsMut::new(self, location)
+    }
+    /// Returns an iterator over the directly adjacent tiles of the specified tile along with their locations, skipping the diagonal ones, in the order ↑, →, ↓, ←.
+    #[inline(always)]
+    pub const fn orthogonal_neighbors(&self, location: FieldCoordinates) -> Neighbors<'_, Ct, Cf> {
+        Neighbors::orthogonal(self, location)
+    }
+    /// Returns an iterator over mutable references to the directly adjacent tiles of the specified tile along with their locations, skipping the diagonal ones, in the order ↑, →, ↓, ←.
+    #[inline(always)]
+    pub fn orthogonal_neighbors_mut(&mut self, location: FieldCoordinates) -> NeighborsMut<'_, Ct, Cf> {
+        NeighborsMut::orthogonal(self, location)
+    }
+    /// Returns `true` if the location is inside the field, `false` otherwise.
+    #[inline]
+    pub const fn contains(&self, coordinates: FieldCoordinates) -> bool {
+        coordinates[0] < self.dimensions[0].get() && coordinates[1] < self.dimensions[1].get()
     }
     /// Detects whether a location is a mine, or `None` if it's out of bounds.
     #[inline]
     pub fn is_mine(&self, location: FieldCoordinates) -> Option<bool> {
-        let (width, height) = (self.dimensions[0].get(), self.dimensions[1].get());
-
-        if location[0] > width || location[1] > height {
-            return None;
-        }
-        let tile = &self[location];
-        if let TileState::Mine(_) = tile.state {
-            Some(true)
-        } else {Some(false)}
+        self.get(location).map(|tile| tile.state.is_mine())
     }
 
     /// Returns the tile at the column `index.0` and row `index.1`, both starting at zero, or `None` if the index is out of bounds.
@@ -235,7 +411,7 @@ impl<Ct, Cf> Field<Ct, Cf> {
         let (width, height) = (self.dimensions[0].get(), self.dimensions[1].get());
         let (x, y) = (coordinates[0], coordinates[1]);
 
-        if x > width || y > height {return None};
+        if x >= width || y >= height {return None}
         Some(unsafe{self.storage.get_unchecked(
               x
             + y * width
@@ -249,7 +425,7 @@ impl<Ct, Cf> Field<Ct, Cf> {
         let (width, height) = (self.dimensions[0].get(), self.dimensions[1].get());
         let (x, y) = (coordinates[0], coordinates[1]);
 
-        if x > width || y > height {return None};
+        if x >= width || y >= height {return None}
         Some(unsafe{self.storage.get_unchecked_mut(
             x
           + y * width
@@ -257,147 +433,237 @@ impl<Ct, Cf> Field<Ct, Cf> {
     }
     /// Returns the outcome of clicking the specified tile **without affecting the field**, or `None` if the index is out of bounds.
     pub fn peek(&self, coordinates: FieldCoordinates) -> Option<ClickOutcome> {
-        if let Some(tile) = self.get(coordinates) {
-            if let Some(outcome) = tile.state.peek_local() {
-                Some(outcome)
-            } else {
-                let neighbors = self.count_neighboring_mines(coordinates);
-                if neighbors > 0 {
-                    Some(ClickOutcome::OpenNumber( unsafe {
-                        NonZeroU8::new_unchecked(neighbors)
-                    })) // We can go for unsafe here since being in this branch implies the check.
-                } else {
-                    Some(ClickOutcome::OpenClearing)
-                }
-            }
-        } else {None}
+        grid::peek(self, coordinates)
     }
     /// Opens **exactly one** tile and returns the outcome of clicking it. **Chords and clearings are not handled** and must be executed manually.
     ///
     /// Essentially, this replaces a `ClosedEmpty` tile with either an `OpenEmpty` or an `OpenNumber` tile.
     pub fn open(&mut self, coordinates: FieldCoordinates) -> Option<ClickOutcome> {
-        if let Some(outcome) = self.peek(coordinates) {
-            match outcome {
-                ClickOutcome::OpenClearing => self[coordinates].state = TileState::OpenEmpty,
-                ClickOutcome::OpenNumber(num) => self[coordinates].state = TileState::OpenNumber(num),
-                _ => {}
-            };
-            Some(outcome)
-        } else {None}
+        grid::open(self, coordinates)
     }
-    /// Performs a chord on the specified tile and returns the [outcomes][chord_outcome] for all 8 tiles touched.
+    /// Opens the specified tile as the first click of a game, relocating the mine on it if there is one, and returns the outcome of clicking it, or `None` if the index is out of bounds.
+    ///
+    /// This follows the behavior of the classic Windows version of Minesweeper: if the first clicked tile contains a mine, the mine is moved to the first tile without a mine in row-major order which isn't void (i.e. the top-left free tile), after which the clicked tile is opened as if it never had a mine. The flag state of both tiles is retained. If the field is completely filled with mines, nothing is relocated and the click results in an explosion. See [`FirstClickOutcome`][fco] for how the relocation is reported.
+    ///
+    /// Just like [`open`][m_open], **chords and clearings are not handled** and must be executed manually.
     ///
-    /// Chord operations in Minesweeper are special convenience operations ran on number tiles. If the amount of mines around a number tile (displayed on its number) is exactly equal to the amount of flags around it, all other tiles can be opened, causing a gameover condition if the flags were placed incorrectly. This method performs just that: counts the surrounding flags and mines and opens the unflagged tiles if these two metrics match.
+    /// # Usage
+    /// ```
+    /// # use sweeper::{Field, TileState, Flag, ClickOutcome, FirstClickOutcome};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(5).unwrap(); 2]);
+    /// field[[0, 0]].state = TileState::Mine(Flag::NotFlagged);
+    /// field[[2, 2]].state = TileState::Mine(Flag::NotFlagged);
+    /// // Clicking a mine on the first click moves it to the top-left free tile.
+    /// let outcome = field.open_first([2, 2]).unwrap();
+    /// assert_eq!(outcome, FirstClickOutcome {
+    ///     outcome: ClickOutcome::OpenClearing,
+    ///     relocated_mine: Some([1, 0]),
+    /// });
+    /// assert!(field[[1, 0]].state.is_mine());
+    /// ```
+    ///
+    /// [fco]: struct.FirstClickOutcome.html "FirstClickOutcome — the outcome of the first click of a game"
+    /// [m_open]: #method.open "open — opens exactly one tile and returns the outcome of clicking it"
+    pub fn open_first(&mut self, coordinates: FieldCoordinates) -> Option<FirstClickOutcome> {
+        let relocated_mine = grid::relocate_mine(self, coordinates);
+        self.open(coordinates)
+            .map(|outcome| FirstClickOutcome {outcome, relocated_mine})
+    }
+    /// Performs a chord on the specified tile and returns the [outcomes][chord_outcome] for all of its neighbors.
+    ///
+    /// Chord operations in Minesweeper are special convenience operations ran on number tiles. If the amount of mines around a number tile (displayed on its number) is exactly equal to the amount of flags around it, all other tiles can be opened, causing a gameover condition if the flags were placed incorrectly. This method performs just that: counts the surrounding flags and mines and opens the closed unflagged tiles if these two metrics match. Just like with [`open`][m_open], every tile is opened on its own, and the clearings reported by the outcomes must be opened manually. The outcome is `Nothing` for tiles which weren't opened and for the directions which lead outside the field. See [`try_chord`][m_try_chord] for a version which reports why the chord couldn't be performed.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{Field, TileState, Flag, ClickOutcome};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// // Chord on every tile next to exactly one mine, on every field up to 4x3 with a single mine.
+    /// for width in 1..=4 {
+    ///     for height in 1..=3 {
+    ///         for mine in 0..width * height {
+    ///             let mine = [mine % width, mine / width];
+    ///             for y in 0..height {
+    ///                 for x in 0..width {
+    ///                     let mut field = Field::<(), ()>::empty([NonZeroUsize::new(width).unwrap(), NonZeroUsize::new(height).unwrap()]);
+    ///                     field[mine].state = TileState::Mine(Flag::Flagged);
+    ///                     if field.count_neighboring_mines([x, y]) != 1 || mine == [x, y] {continue}
+    ///                     field.open([x, y]);
+    ///                     let outcome = field.chord([x, y]);
+    ///                     // Every neighbor except the flagged mine was opened, in clockwise order.
+    ///                     let opened: Vec<_> = field.neighbor_locations([x, y])
+    ///                         .filter(|&location| location != mine)
+    ///                         .collect();
+    ///                     assert_eq!(outcome.iter().filter(|&&o| o != ClickOutcome::Nothing).count(), opened.len());
+    ///                     for location in opened {
+    ///                         assert!(field[location].state.is_open());
+    ///                     }
+    ///                     assert!(!outcome.contains(&ClickOutcome::Explosion));
+    ///                 }
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
     ///
     /// [chord_outcome]: type.ChordOutcome.html "ChordOutcome — the outcome of a chord operation"
-    #[allow(clippy::redundant_closure_call)] // This lint shall not be a thing.
+    /// [m_open]: #method.open "open — opens exactly one tile and returns the outcome of clicking it"
+    /// [m_try_chord]: #method.try_chord "try_chord — performs a chord on the specified tile, or returns an error if the chord can't be performed"
     pub fn chord(&mut self, coordinates: FieldCoordinates) -> ChordOutcome {
-        let (x, y) = (coordinates[0], coordinates[1]);
-
-        let mut result = [ClickOutcome::Nothing; 8];
-        let num_mines = if let Some(tile) = self.get(coordinates) {
-            if let TileState::OpenNumber(num_mines) = tile.state {
-                num_mines.get()
-            } else {return result}
-        } else {return result};
-
-        let mut num_flags = 0_u8;
-        let mut ckflag = |coords: FieldCoordinates| {
-            if let Some(tile) = self.get(coords) {
-                if tile.state.is_flagged() {
-                    num_flags += 1;
-                }
-            }
-        };
-        ckflag([x - 1, y + 1]); // Up-left,
-        ckflag([x    , y + 1]); // up,
-        ckflag([x + 1, y + 1]); // up-right,
-        ckflag([x + 1, y    ]); // right,
-        ckflag([x + 1, y - 1]); // down-right,
-        ckflag([x    , y - 1]); // down,
-        ckflag([x - 1, y - 1]); // down-left,
-        ckflag([x - 1, y    ]); // and left.
-
-        if num_flags != num_mines {
-            return result // We can't chord without enough flags or with too many.
-        };
-
-        let calc_result = |coords: FieldCoordinates| {
-            if let Some(tile) = self.get(coords) {
-                if !tile.state.is_flagged() {
-                self.peek(coords).unwrap_or_default()
-                } else {
-                    ClickOutcome::Nothing
-                }
-            } else {
-                ClickOutcome::Nothing
-            }
-        };
-        result[0] = calc_result([x    , y + 1]);
-        result[1] = calc_result([x + 1, y + 1]);
-        result[2] = calc_result([x + 1, y    ]);
-        result[3] = calc_result([x + 1, y - 1]);
-        result[4] = calc_result([x    , y - 1]);
-        result[5] = calc_result([x - 1, y - 1]);
-        result[6] = calc_result([x - 1, y    ]);
-        result[7] = calc_result([x - 1, y + 1]);
-
-        result
+        // We can't chord without enough flags or with too many.
+        self.try_chord(coordinates)
+            .unwrap_or_else(|_| alloc::vec![ClickOutcome::Nothing; self.neighborhood.len()])
     }
     /// Performs a chord on the specified tile recursively, i.e. runs chords for all number tiles which were uncovered from chording.
     ///
-    /// The returned value contains one entry per chord operation
+    /// The returned value contains one entry per chord operation: the first one is the chord on the specified tile, and the following ones are the chords on the number tiles it uncovered, recursively, which opened at least one tile. Just like with [`chord`][m_chord], the clearings reported by the outcomes must be opened manually.
+    ///
+    /// [m_chord]: #method.chord "chord — performs a chord operation on the specified tile"
     #[must_use = "recursive chords incur a heapstack allocation and are generally slow"]
     pub fn recursive_chord(&mut self, index: FieldCoordinates) -> Vec<RecursiveChordOutcome> {
-        // Similar to the clearing algorithm, we're using a stack frame type here.
-        // The meanings of values are pretty similar to the ones seen there.
-        // The key difference is that the second element is an array for the sake of readability.
-        // The directions are, in this exact order, up-left, up, up-right, right, down-right, down, down-left, left.
-        type StackFrame = (FieldCoordinates, [bool; 8]);
-        // We're gonna need less recursion depth here.
-        let mut stack = Vec::<StackFrame>::with_capacity(8);
-        let mut stack_top = (index, [true; 8]);
-
-        // The return value will be stored as a Vec of all the chord outcomes coupled with the coordinates at which they occurred.
-        let mut chord_outcomes = Vec::<RecursiveChordOutcome>::with_capacity(8);
-        loop {
-            let chosen_location =
-                 if stack_top.1[0] {stack_top.1[0] = false; 0}
-            else if stack_top.1[1] {stack_top.1[1] = false; 1}
-            else if stack_top.1[2] {stack_top.1[2] = false; 2}
-            else if stack_top.1[3] {stack_top.1[3] = false; 3}
-            else if stack_top.1[4] {stack_top.1[4] = false; 4}
-            else if stack_top.1[5] {stack_top.1[5] = false; 5}
-            else if stack_top.1[6] {stack_top.1[6] = false; 6}
-            else if stack_top.1[7] {stack_top.1[7] = false; 7}
-            else if let Some(new_top) = stack.pop() {
-                stack_top = new_top;
-                continue;
-            } else {break};
-
-            let location_to_chord = match chosen_location {
-                0 => [stack_top.0[0] - 1, stack_top.0[1] + 1], // Up & left,
-                1 => [stack_top.0[0]    , stack_top.0[1] + 1], // up,
-                2 => [stack_top.0[0] + 1, stack_top.0[1] + 1], // up & right,
-                3 => [stack_top.0[0] + 1, stack_top.0[1]    ], // right,
-                4 => [stack_top.0[0] + 1, stack_top.0[1] - 1], // down & right,
-                5 => [stack_top.0[0]    , stack_top.0[1] - 1], // down,
-                6 => [stack_top.0[0] - 1, stack_top.0[1] - 1], // down & left,
-                7 => [stack_top.0[0] - 1, stack_top.0[1]    ], // and left.
-                _ => unreachable!()
-            };
-
-            let outcome = self.chord(location_to_chord);
-            chord_outcomes.push((location_to_chord, outcome));
-            if !(outcome == [ClickOutcome::Nothing; 8]) {
-                stack.push(stack_top);
-                stack_top = (location_to_chord, [true; 8]);
-                continue;
+        let mut chord_outcomes = alloc::vec![(index, self.chord(index))];
+        // The outcomes double as the queue of chords whose uncovered numbers are yet to be chorded.
+        let mut next = 0;
+        while let Some((location, outcome)) = chord_outcomes.get(next).cloned() {
+            next += 1;
+            for (index, tile_outcome) in outcome.iter().enumerate() {
+                if let ClickOutcome::OpenNumber(_) = tile_outcome {
+                    if let Some(uncovered) = neighbor(self.dimensions, self.topology, self.neighborhood, location, index) {
+                        let outcome = self.chord(uncovered);
+                        if outcome.iter().any(|&tile_outcome| tile_outcome != ClickOutcome::Nothing) {
+                            chord_outcomes.push((uncovered, outcome));
+                        }
+                    }
+                }
             }
-        };
+        }
         chord_outcomes
     }
 
+    /// Returns the tile at the specified location, or an error if the location is out of bounds.
+    ///
+    /// This is the fallible version of [`get`][m_get].
+    ///
+    /// # Errors
+    /// Returns [`OutOfBounds`][e_oob] if the location is outside the field.
+    ///
+    /// [m_get]: #method.get "get — returns the tile at the specified location, or None if the index is out of bounds"
+    /// [e_oob]: enum.Error.html#variant.OutOfBounds "Error::OutOfBounds — the specified location is outside the field"
+    #[inline]
+    pub fn try_get(&self, coordinates: FieldCoordinates) -> Result<&Tile<Ct, Cf>, Error> {
+        if !self.contains(coordinates) {return Err(Error::OutOfBounds(coordinates))}
+        Ok(&self[coordinates])
+    }
+    /// Returns a mutable reference to the tile at the specified location, or an error if the location is out of bounds.
+    ///
+    /// This is the fallible version of [`get_mut`][m_get_mut].
+    ///
+    /// # Errors
+    /// Returns [`OutOfBounds`][e_oob] if the location is outside the field.
+    ///
+    /// [m_get_mut]: #method.get_mut "get_mut — returns a mutable reference to the tile at the specified location, or None if the index is out of bounds"
+    /// [e_oob]: enum.Error.html#variant.OutOfBounds "Error::OutOfBounds — the specified location is outside the field"
+    #[inline]
+    pub fn try_get_mut(&mut self, coordinates: FieldCoordinates) -> Result<&mut Tile<Ct, Cf>, Error> {
+        if !self.contains(coordinates) {return Err(Error::OutOfBounds(coordinates))}
+        Ok(&mut self[coordinates])
+    }
+    /// Detects whether a location is a mine, or returns an error if the location is out of bounds.
+    ///
+    /// This is the fallible version of [`is_mine`][m_is_mine].
+    ///
+    /// # Errors
+    /// Returns [`OutOfBounds`][e_oob] if the location is outside the field.
+    ///
+    /// [m_is_mine]: #method.is_mine "is_mine — detects whether a location is a mine, or None if it's out of bounds"
+    /// [e_oob]: enum.Error.html#variant.OutOfBounds "Error::OutOfBounds — the specified location is outside the field"
+    #[inline]
+    pub fn try_is_mine(&self, location: FieldCoordinates) -> Result<bool, Error> {
+        self.try_get(location).map(|tile| tile.state.is_mine())
+    }
+    /// Returns the outcome of clicking the specified tile **without affecting the field**, or an error if the location is out of bounds.
+    ///
+    /// This is the fallible version of [`peek`][m_peek].
+    ///
+    /// # Errors
+    /// Returns [`OutOfBounds`][e_oob] if the location is outside the field.
+    ///
+    /// [m_peek]: #method.peek "peek — returns the outcome of clicking the specified tile without affecting the field"
+    /// [e_oob]: enum.Error.html#variant.OutOfBounds "Error::OutOfBounds — the specified location is outside the field"
+    pub fn try_peek(&self, coordinates: FieldCoordinates) -> Result<ClickOutcome, Error> {
+        self.try_get(coordinates)?;
+        Ok(self.peek(coordinates).unwrap_or_default())
+    }
+    /// Opens **exactly one** tile and returns the outcome of clicking it, or returns an error if the tile can't be opened. **Chords and clearings are not handled** and must be executed manually.
+    ///
+    /// This is the fallible version of [`open`][m_open]. Opening a mine is not an error, since it's a regular outcome of a click.
+    ///
+    /// # Errors
+    /// Returns [`OutOfBounds`][e_oob] if the location is outside the field and [`AlreadyOpen`][e_ao] if the tile is already open.
+    ///
+    /// [m_open]: #method.open "open — opens exactly one tile and returns the outcome of clicking it"
+    /// [e_oob]: enum.Error.html#variant.OutOfBounds "Error::OutOfBounds — the specified location is outside the field"
+    /// [e_ao]: enum.Error.html#variant.AlreadyOpen "Error::AlreadyOpen — the tile is already open"
+    pub fn try_open(&mut self, coordinates: FieldCoordinates) -> Result<ClickOutcome, Error> {
+        if self.try_get(coordinates)?.state.is_open() {
+            return Err(Error::AlreadyOpen(coordinates));
+        }
+        Ok(self.open(coordinates).unwrap_or_default())
+    }
+    /// Opens the specified tile as the first click of a game, relocating the mine on it if there is one, or returns an error if the tile can't be opened.
+    ///
+    /// This is the fallible version of [`open_first`][m_of].
+    ///
+    /// # Errors
+    /// Returns [`OutOfBounds`][e_oob] if the location is outside the field and [`AlreadyOpen`][e_ao] if the tile is already open.
+    ///
+    /// [m_of]: #method.open_first "open_first — opens the specified tile as the first click of a game, relocating the mine on it if there is one"
+    /// [e_oob]: enum.Error.html#variant.OutOfBounds "Error::OutOfBounds — the specified location is outside the field"
+    /// [e_ao]: enum.Error.html#variant.AlreadyOpen "Error::AlreadyOpen — the tile is already open"
+    pub fn try_open_first(&mut self, coordinates: FieldCoordinates) -> Result<FirstClickOutcome, Error> {
+        if self.try_get(coordinates)?.state.is_open() {
+            return Err(Error::AlreadyOpen(coordinates));
+        }
+        self.open_first(coordinates).ok_or(Error::OutOfBounds(coordinates))
+    }
+    /// Performs a chord on the specified tile and returns the [outcomes][chord_outcome] for all tiles in the field's neighborhood, or returns an error if the chord can't be performed.
+    ///
+    /// This is the fallible version of [`chord`][m_chord], which returns `Nothing` for all tiles in these cases.
+    ///
+    /// # Errors
+    /// Returns [`OutOfBounds`][e_oob] if the location is outside the field, [`NotANumber`][e_nan] if the tile is not an open number tile and [`FlagCountMismatch`][e_fcm] if the amount of flags around the tile is different from its number.
+    ///
+    /// [chord_outcome]: type.ChordOutcome.html "ChordOutcome — the outcome of a chord operation"
+    /// [m_chord]: #method.chord "chord — performs a chord operation on the specified tile"
+    /// [e_oob]: enum.Error.html#variant.OutOfBounds "Error::OutOfBounds — the specified location is outside the field"
+    /// [e_nan]: enum.Error.html#variant.NotANumber "Error::NotANumber — a chord was performed on a tile which isn't an open number tile"
+    /// [e_fcm]: enum.Error.html#variant.FlagCountMismatch "Error::FlagCountMismatch — a chord was performed on a number tile with a different amount of flags around it"
+    pub fn try_chord(&mut self, coordinates: FieldCoordinates) -> Result<ChordOutcome, Error> {
+        let mut result = alloc::vec![ClickOutcome::Nothing; self.neighborhood.len()];
+        grid::chord(self, coordinates, &mut result)?;
+        Ok(result)
+    }
+    /// Performs a chord on the specified tile recursively, i.e. runs chords for all number tiles which were uncovered from chording, or returns an error if the first chord can't be performed.
+    ///
+    /// This is the fallible version of [`recursive_chord`][m_rechord]. Only the tile the operation is started on is checked, since the chords which follow are only performed where possible.
+    ///
+    /// # Errors
+    /// See [`try_chord`][m_try_chord].
+    ///
+    /// [m_rechord]: #method.recursive_chord "recursive_chord — performs a chord operation on the specified tile recursively, i.e. runs chords for all number tiles which were uncovered from chording"
+    /// [m_try_chord]: #method.try_chord "try_chord — performs a chord on the specified tile, or returns an error if the chord can't be performed"
+    #[must_use = "recursive chords incur a heapstack allocation and are generally slow"]
+    pub fn try_recursive_chord(&mut self, coordinates: FieldCoordinates) -> Result<Vec<RecursiveChordOutcome>, Error> {
+        self.check_chord(coordinates)?;
+        Ok(self.recursive_chord(coordinates))
+    }
+    /// Checks that a chord can be performed on the specified tile.
+    fn check_chord(&self, coordinates: FieldCoordinates) -> Result<(), Error> {
+        grid::check_chord(self, coordinates)
+    }
+
     /// Returns an iterator over a single row.
     ///
     /// Said iterator can then also be indexed, thus serving as a versatile reference to a specific row.
@@ -419,7 +685,24 @@ impl<Ct, Cf> Field<Ct, Cf> {
         ColumnIter::new(self, column)
     }
 
-    /// Returns an iterator over the field's columns.
+    /// Returns a mutable iterator over a single row.
+    ///
+    /// # Panics
+    /// Panics if the specified row is out of range.
+    #[inline(always)]
+    pub fn row_mut(&mut self, row: usize) -> RowIterMut<'_, Ct, Cf> {
+        RowIterMut::new(self, row)
+    }
+    /// Returns a mutable iterator over a single column.
+    ///
+    /// # Panics
+    /// Panics if the specified column is out of range.
+    #[inline(always)]
+    pub fn column_mut(&mut self, column: usize) -> ColumnIterMut<'_, Ct, Cf> {
+        ColumnIterMut::new(self, column)
+    }
+
+    /// Returns an iterator over the field's rows.
     #[inline(always)]
     pub const fn rows(&self) -> FieldRowsIter<'_, Ct, Cf> {
         FieldRowsIter::new(self)
@@ -429,6 +712,16 @@ impl<Ct, Cf> Field<Ct, Cf> {
     pub const fn columns(&self) -> FieldColumnsIter<'_, Ct, Cf> {
         FieldColumnsIter::new(self)
     }
+    /// Returns an iterator over the field's rows, each of which is a mutable iterator over its tiles.
+    #[inline(always)]
+    pub fn rows_mut(&mut self) -> FieldRowsIterMut<'_, Ct, Cf> {
+        FieldRowsIterMut::new(self)
+    }
+    /// Returns an iterator over the field's columns, each of which is a mutable iterator over its tiles. All of the columns can be borrowed at the same time.
+    #[inline(always)]
+    pub fn columns_mut(&mut self) -> FieldColumnsIterMut<'_, Ct, Cf> {
+        FieldColumnsIterMut::new(self)
+    }
     /// Returns a `Clearing` on the specified `Field`, or `None` if the location has 1 or more neighboring mines or is out of bounds.
     #[inline(always)]
     pub fn clearing(&self, anchor_location: FieldCoordinates) -> Option<Clearing<Ct, Cf>> {
@@ -453,6 +746,36 @@ impl<Ct, Cf> Field<Ct, Cf> {
     pub fn all_tiles_mut(&mut self) -> core::slice::IterMut<'_, Tile<Ct, Cf>>{
         self.storage.iter_mut()
     }
+    /// Returns an iterator over all tiles in the field along with their locations, in row-major order.
+    #[inline(always)]
+    pub fn enumerate_tiles(&self) -> EnumerateTiles<'_, Ct, Cf> {
+        EnumerateTiles::new(self)
+    }
+    /// Returns a mutable iterator over all tiles in the field along with their locations, in row-major order.
+    #[inline(always)]
+    pub fn enumerate_tiles_mut(&mut self) -> EnumerateTilesMut<'_, Ct, Cf> {
+        EnumerateTilesMut::new(self)
+    }
+    /// Returns an iterator over the tiles with mines along with their locations, in row-major order.
+    #[inline(always)]
+    pub fn mines(&self) -> FilteredTiles<'_, Ct, Cf> {
+        FilteredTiles::new(self, TileState::is_mine)
+    }
+    /// Returns an iterator over the closed tiles along with their locations, in row-major order. This includes the tiles with mines.
+    #[inline(always)]
+    pub fn closed_tiles(&self) -> FilteredTiles<'_, Ct, Cf> {
+        FilteredTiles::new(self, TileState::is_closed)
+    }
+    /// Returns an iterator over the tiles with the standard flag along with their locations, in row-major order. Custom flags are not included.
+    #[inline(always)]
+    pub fn flagged_tiles(&self) -> FilteredTiles<'_, Ct, Cf> {
+        FilteredTiles::new(self, TileState::is_flagged)
+    }
+    /// Returns an iterator over the open tiles with numbers along with their locations, in row-major order.
+    #[inline(always)]
+    pub fn open_numbers(&self) -> FilteredTiles<'_, Ct, Cf> {
+        FilteredTiles::new(self, |state| matches!(state, TileState::OpenNumber(_)))
+    }
     /// Consumes the field and returns the underlying storage for the tiles.
     ///
     /// The tiles are stored in row-major order, i.e. the tile in the second column of a row goes right after the tile in the first column, and so on.
@@ -469,69 +792,59 @@ impl<Ct, Cf> Field<Ct, Cf> {
     /// Since the field is modified in an undefined way in the process, it is taken by value.
     ///
     /// [clearing]: struct.Clearing.html "Clearing — a clearing on the specified field"
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{Field, TileState, Flag};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(5).unwrap(), NonZeroUsize::new(1).unwrap()]);
+    /// field[[2, 0]].state = TileState::Mine(Flag::NotFlagged);
+    /// // Two clearings, each with a number on its shore.
+    /// assert_eq!(field.clone().calculate_3bv(), 2);
+    /// field[[0, 0]].state = TileState::Mine(Flag::NotFlagged);
+    /// // A lone number and a clearing.
+    /// assert_eq!(field.calculate_3bv(), 2);
+    /// ```
     #[must_use = "calculating the 3BV value for any possible field requires traversing the entire field two times and opening clearings"]
     pub fn calculate_3bv(mut self) -> usize {
-        let mut result = 0_usize;
-        // First pass: close all clearings.
-        for y in 0..self.dimensions[1].get() {
-            for x in 0..self.dimensions[0].get() {
-                match self[[x, y]].state {
-                    TileState::OpenEmpty
-                  | TileState::OpenNumber(_) => {
-                        self[[x, y]].state = TileState::ClosedEmpty(Flag::NotFlagged)
-                    }
-                    _ => {}
-                };
-            }
-        }
-        // Second pass: count numbered tiles and clearings.
-        for y in 0..self.dimensions[1].get() {
-            for x in 0..self.dimensions[0].get() {
-                match self[[x, y]].state {
-                    TileState::ClosedEmpty(_) => {
-                        let outcome = self.open([x, y])
-                            .expect("unexpected out of index error during 3BV calculation");
-                        match outcome {
-                            ClickOutcome::OpenClearing => {
-                                self.clearing_mut([x, y])
-                                    .expect("unexpected out of index error during 3BV calculation")
-                                    .open(true);
-                                result += 1;
-                            },
-                            ClickOutcome::OpenNumber(_) => {
-                                let belongs_to_a_clearing = |coords: FieldCoordinates| {
-                                    let mut result = false;
-                                    let mut cktile = |coords: FieldCoordinates| {
-                                        if let Some(tile) = self.get(coords) {
-                                            if let TileState::OpenEmpty = tile.state {result = true;}
-                                            else {}
-                                        } else {}
-                                    };
-                                    let [x, y] = coords;
-
-                                    cktile([x - 1, y + 1]); // Up-left,
-                                    cktile([x    , y + 1]); // up,
-                                    cktile([x + 1, y + 1]); // up-right,
-                                    cktile([x + 1, y    ]); // right,
-                                    cktile([x + 1, y - 1]); // down-right,
-                                    cktile([x    , y - 1]); // down,
-                                    cktile([x - 1, y - 1]); // down-left,
-                                    cktile([x - 1, y    ]); // and left.
-                                    result
-                                };
-                                if belongs_to_a_clearing([x, y]) {
-                                    result += 1;
-                                }
-                            },
-                            _ => {}
-                        };
-                    },
-                    TileState::OpenNumber(_) => result += 1,
-                    _ => {}
-                };
-            }
+        grid::calculate_3bv(&mut self)
+    }
+}
+impl<Ct, Cf> Grid for Field<Ct, Cf> {
+    type Location = FieldCoordinates;
+    type Payload = Ct;
+    type Flag = Cf;
+    #[inline(always)]
+    fn area(&self) -> usize {
+        self.storage.len()
+    }
+    #[inline(always)]
+    fn index_of(&self, location: FieldCoordinates) -> usize {
+        location[0] + location[1] * self.dimensions[0].get()
+    }
+    #[inline(always)]
+    fn location_of(&self, index: usize) -> FieldCoordinates {
+        [index % self.dimensions[0].get(), index / self.dimensions[0].get()]
+    }
+    #[inline(always)]
+    fn neighbor_count(&self) -> usize {
+        self.neighborhood.len()
+    }
+    #[inline(always)]
+    fn neighbor(&self, center: FieldCoordinates, direction: usize) -> Option<FieldCoordinates> {
+        neighbor(self.dimensions, self.topology, self.neighborhood, center, direction)
+    }
+    #[inline(always)]
+    fn tile(&self, location: FieldCoordinates) -> Option<&Tile<Ct, Cf>> {
+        self.get(location)
+    }
+    #[inline]
+    fn update_state<F>(&mut self, location: FieldCoordinates, update: F)
+    where F: FnOnce(TileState<Cf>) -> TileState<Cf> {
+        if let Some(tile) = self.get_mut(location) {
+            tile.state = update(mem::take(&mut tile.state));
         }
-        result
     }
 }
 impl<Ct, Cf> Index<FieldCoordinates> for Field<Ct, Cf> {
@@ -556,14 +869,104 @@ impl<Ct, Cf> IndexMut<FieldCoordinates> for Field<Ct, Cf> {
         self.get_mut(coordinates).expect("index out of bounds")
     }
 }
+/// Checks that the storage has as many tiles as the dimensions require.
+#[cfg(feature = "serialization")]
+fn check_storage_length(dimensions: FieldDimensions, length: usize) -> Result<(), InvalidFieldError> {
+    let area = dimensions[0].get().checked_mul(dimensions[1].get())
+        .ok_or(InvalidFieldError::AreaOverflow)?;
+    if length != area {
+        return Err(InvalidFieldError::StorageLengthMismatch {expected: area, found: length});
+    }
+    Ok(())
+}
+
+/// The error type for [field validation][m_validate], also reported when deserializing an invalid field.
+///
+/// Just like with [`Error`][error], the type parameter is the type of the reported locations, which is [`CubeCoordinates`][cc] for a [`CubeField`][cube].
+///
+/// [m_validate]: struct.Field.html#method.validate "validate — checks that the numbers on all open tiles match the amount of mines around them"
+/// [error]: enum.Error.html "Error — the error type for the fallible versions of field operations"
+/// [cube]: cube/struct.CubeField.html "CubeField — a three-dimensional playfield"
+/// [cc]: cube/type.CubeCoordinates.html "CubeCoordinates — the coordinates of a tile on a three-dimensional field"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum InvalidFieldError<L = FieldCoordinates> {
+    /// The area of the field doesn't fit into `usize`.
+    AreaOverflow,
+    /// The storage has a different amount of tiles than the dimensions require.
+    StorageLengthMismatch {
+        /// The area of the field.
+        expected: usize,
+        /// The amount of tiles in the storage.
+        found: usize,
+    },
+    /// An open tile shows a different number than the amount of mines around it.
+    InconsistentNumber {
+        /// The coordinates of the tile.
+        location: L,
+        /// The actual amount of mines around the tile.
+        neighboring_mines: u8,
+    },
+}
+impl<L: fmt::Debug> fmt::Display for InvalidFieldError<L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AreaOverflow => write!(f,
+                "the area of the field is too big"
+            ),
+            Self::StorageLengthMismatch {expected, found} => write!(f,
+                "expected {expected} tiles for the dimensions of the field, found {found}"
+            ),
+            Self::InconsistentNumber {location, neighboring_mines} => write!(f,
+                "the number on the open tile at {location:?} doesn't match the {neighboring_mines} mines around it"
+            ),
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl<L: fmt::Debug> std::error::Error for InvalidFieldError<L> {}
+
+/// The current version of the layout used to serialize fields.
+///
+/// Serialized fields start with this version, which allows future releases to load fields saved by older ones: every change to the layout bumps the version and adds a migration from the previous one. Fields without a version, saved by releases from before it was introduced, are still accepted in self-describing formats like JSON. Fields saved by a newer release with an unknown version are rejected instead of being misinterpreted.
+#[cfg(feature = "serialization")]
+pub const FIELD_SERIALIZATION_VERSION: u32 = 3;
+
+/// The dimensions, storage, topology and neighborhood of a field, in the current layout.
+#[cfg(feature = "serialization")]
+type FieldParts<Ct, Cf> = (FieldDimensions, Vec<Tile<Ct, Cf>>, Topology, Neighborhood);
+/// Converts the dimensions, storage, topology and neighborhood of a field saved with the specified version of the layout to the current one, or returns `None` if the version is not supported.
+///
+/// Version 0 stands for fields saved before the version was introduced, which have the same layout as version 1. Version 2 added the topology, so all older fields are bounded, and version 3 added the neighborhood, so all older fields use the Moore neighborhood.
+#[cfg(feature = "serialization")]
+fn migrate<Ct, Cf>(version: u32, dimensions: FieldDimensions, storage: Vec<Tile<Ct, Cf>>, topology: Option<Topology>, neighborhood: Option<Neighborhood>) -> Option<FieldParts<Ct, Cf>> {
+    match version {
+        0 | 1 => Some((dimensions, storage, Topology::Bounded, Neighborhood::MOORE)),
+        2 => Some((dimensions, storage, topology.unwrap_or_default(), Neighborhood::MOORE)),
+        3 => Some((dimensions, storage, topology.unwrap_or_default(), neighborhood.unwrap_or_default())),
+        _ => None,
+    }
+}
+#[cfg(feature = "serialization")]
+fn unsupported_version<E: serde::de::Error>(version: u32) -> E {
+    E::invalid_value(
+        serde::de::Unexpected::Unsigned(version.into()),
+        &"a field serialization version supported by this release",
+    )
+}
 #[cfg(feature = "serialization")]
 impl<Ct, Cf> Serialize for Field<Ct, Cf>
 where Ct: Serialize,
       Cf: Serialize {
+    /// Serializes the field together with the [current version][version] of the layout.
+    ///
+    /// [version]: constant.FIELD_SERIALIZATION_VERSION.html "FIELD_SERIALIZATION_VERSION — the current version of the layout used to serialize fields"
     fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
-       let mut s = s.serialize_struct("Field", 2)?;
+       let mut s = s.serialize_struct("Field", 5)?;
+       s.serialize_field("version", &FIELD_SERIALIZATION_VERSION)?;
        s.serialize_field("dimensions", &self.dimensions)?;
        s.serialize_field("storage", &self.storage)?;
+       s.serialize_field("topology", &self.topology)?;
+       s.serialize_field("neighborhood", &self.neighborhood)?;
        s.end()
     }
 }
@@ -571,10 +974,17 @@ where Ct: Serialize,
 impl<'de, Ct, Cf> Deserialize<'de> for Field<Ct, Cf>
 where Ct: Deserialize<'de>,
       Cf: Deserialize<'de> {
+    /// Deserializes a field saved with the current or an older [version][version] of the layout and [validates][m_validate] it.
+    ///
+    /// Unknown entries in self-describing formats are skipped, so that future releases can add optional data without breaking older ones.
+    ///
+    /// [version]: constant.FIELD_SERIALIZATION_VERSION.html "FIELD_SERIALIZATION_VERSION — the current version of the layout used to serialize fields"
+    /// [m_validate]: struct.Field.html#method.validate "validate — checks that the numbers on all open tiles match the amount of mines around them"
+    #[allow(clippy::too_many_lines)] // Most of it is the boilerplate of a hand-written visitor.
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
         use serde::de;
-        const FIELDS: &[&str] = &["storage", "dimensions"];
-        enum StructField { Storage, Dimensions };
+        const FIELDS: &[&str] = &["version", "dimensions", "storage", "topology", "neighborhood"];
+        enum StructField { Version, Dimensions, Storage, Topology, Neighborhood, Unknown }
 
         // This part could also be generated independently by:
         //
@@ -589,14 +999,17 @@ where Ct: Deserialize<'de>,
                     type Value = StructField;
 
                     fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
-                        formatter.write_str("`storage` or `dimensions`")
+                        formatter.write_str("`version`, `dimensions`, `storage`, `topology` or `neighborhood`")
                     }
 
                     fn visit_str<E: de::Error>(self, value: &str) -> Result<StructField, E> {
                         match value {
-                            "storage" => Ok(StructField::Storage),
+                            "version" => Ok(StructField::Version),
                             "dimensions" => Ok(StructField::Dimensions),
-                            _ => Err(de::Error::unknown_field(value, FIELDS)),
+                            "storage" => Ok(StructField::Storage),
+                            "topology" => Ok(StructField::Topology),
+                            "neighborhood" => Ok(StructField::Neighborhood),
+                            _ => Ok(StructField::Unknown),
                         }
                     }
                 }
@@ -607,6 +1020,16 @@ where Ct: Deserialize<'de>,
 
         struct FieldVisitor<Ct, Cf>(PhantomData<(Ct, Cf)>);
 
+        impl<Ct, Cf> FieldVisitor<Ct, Cf> {
+            fn finish<E: de::Error>(version: u32, dimensions: FieldDimensions, storage: Vec<Tile<Ct, Cf>>, topology: Option<Topology>, neighborhood: Option<Neighborhood>) -> Result<Field<Ct, Cf>, E> {
+                let (dimensions, storage, topology, neighborhood) = migrate(version, dimensions, storage, topology, neighborhood)
+                    .ok_or_else(|| unsupported_version(version))?;
+                check_storage_length(dimensions, storage.len()).map_err(E::custom)?;
+                let field = Field {dimensions, storage, topology, neighborhood};
+                field.validate().map_err(E::custom)?;
+                Ok(field)
+            }
+        }
         impl<'de, Ct, Cf> Visitor<'de> for FieldVisitor<Ct, Cf>
         where Ct: Deserialize<'de>,
               Cf: Deserialize<'de> {
@@ -617,18 +1040,39 @@ where Ct: Deserialize<'de>,
             }
 
             fn visit_seq<V: SeqAccess<'de>>(self, mut seq: V) -> Result<Self::Value, V::Error> {
-                let dimensions = seq.next_element()?
+                let version = seq.next_element()?
                     .ok_or_else(|| de::Error::invalid_length(0, &self))?;
-                let storage = seq.next_element()?
+                // Check the version right away, since the rest of a newer layout can't be read as the current one.
+                if version > FIELD_SERIALIZATION_VERSION {
+                    return Err(unsupported_version(version));
+                }
+                let dimensions = seq.next_element()?
                     .ok_or_else(|| de::Error::invalid_length(1, &self))?;
-                Ok(Field {dimensions, storage})
+                let storage = seq.next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                let topology = if version >= 2 {
+                    Some(seq.next_element()?.ok_or_else(|| de::Error::invalid_length(3, &self))?)
+                } else {None};
+                let neighborhood = if version >= 3 {
+                    Some(seq.next_element()?.ok_or_else(|| de::Error::invalid_length(4, &self))?)
+                } else {None};
+                Self::finish(version, dimensions, storage, topology, neighborhood)
             }
 
             fn visit_map<V: MapAccess<'de>>(self, mut map: V) -> Result<Self::Value, V::Error> {
+                let mut version: Option<u32> = None;
                 let mut dimensions: Option<FieldDimensions> = None;
                 let mut storage: Option<Vec<Tile<Ct, Cf>>> = None;
+                let mut topology: Option<Topology> = None;
+                let mut neighborhood: Option<Neighborhood> = None;
                 while let Some(key) = map.next_key()? {
                     match key {
+                        StructField::Version => {
+                            if version.is_some() {
+                                return Err(de::Error::duplicate_field("version"));
+                            }
+                            version = Some(map.next_value()?);
+                        }
                         StructField::Dimensions => {
                             if dimensions.is_some() {
                                 return Err(de::Error::duplicate_field("dimensions"));
@@ -641,13 +1085,28 @@ where Ct: Deserialize<'de>,
                             }
                             storage = Some(map.next_value()?);
                         }
+                        StructField::Topology => {
+                            if topology.is_some() {
+                                return Err(de::Error::duplicate_field("topology"));
+                            }
+                            topology = Some(map.next_value()?);
+                        }
+                        StructField::Neighborhood => {
+                            if neighborhood.is_some() {
+                                return Err(de::Error::duplicate_field("neighborhood"));
+                            }
+                            neighborhood = Some(map.next_value()?);
+                        }
+                        StructField::Unknown => {
+                            map.next_value::<de::IgnoredAny>()?;
+                        }
                     }
                 }
                 let dimensions = dimensions.ok_or_else(|| de::Error::missing_field("dimensions"))?;
                 let storage = storage.ok_or_else(|| de::Error::missing_field("storage"))?;
-                Ok(Field {dimensions, storage})
+                Self::finish(version.unwrap_or(0), dimensions, storage, topology, neighborhood)
             }
         }
         d.deserialize_struct("Field", FIELDS, FieldVisitor(PhantomData))
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,620 @@
+//! A game session on top of a field, which keeps track of the phase of the game, the time and the flags.
+
+use core::{
+    cell::Cell,
+    time::Duration,
+    mem,
+};
+use alloc::vec::Vec;
+#[cfg(feature = "serialization")]
+use serde::{Serialize, Deserialize};
+use crate::{
+    Field, FieldCoordinates, ChordOutcome, RecursiveChordOutcome,
+    Tile, TileState, Flag, ClickOutcome,
+    Journal, JournalEntry, TileChange, Action,
+    Replay, ReplayEvent, ReplayAction,
+    iter::neighbor,
+    grid::{self, Grid},
+};
+
+/// A source of time for a [game session][game].
+///
+/// The crate doesn't assume anything about the platform, which is why the time is provided by the frontend through this trait. On hosted platforms, [`StdClock`][stdclock] can be used; freestanding environments can implement the trait on top of a hardware timer or use a [`ManualClock`][manual] and advance it from a timer interrupt.
+///
+/// [game]: struct.Game.html "Game — a Minesweeper game session"
+/// [stdclock]: struct.StdClock.html "StdClock — a clock based on the monotonic clock of the standard library"
+/// [manual]: struct.ManualClock.html "ManualClock — a clock which only advances when explicitly told to"
+pub trait Clock {
+    /// Returns the time elapsed since an arbitrary point in the past, which must not change during the lifetime of the clock.
+    ///
+    /// The returned value must never decrease between calls.
+    fn now(&self) -> Duration;
+}
+impl<C: Clock + ?Sized> Clock for &C {
+    #[inline(always)]
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+}
+
+/// A clock based on the monotonic clock of the standard library.
+///
+/// The time is measured from the creation of the clock.
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug)]
+pub struct StdClock {
+    origin: std::time::Instant,
+}
+#[cfg(feature = "std")]
+impl StdClock {
+    /// Creates a clock which starts counting time from the moment of the call.
+    #[inline]
+    pub fn new() -> Self {
+        Self {origin: std::time::Instant::now()}
+    }
+}
+#[cfg(feature = "std")]
+impl Default for StdClock {
+    /// Creates a clock which starts counting time from the moment of the call.
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+#[cfg(feature = "std")]
+impl Clock for StdClock {
+    #[inline]
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+/// A clock which only advances when explicitly told to.
+///
+/// Useful for freestanding environments, where the frontend can advance the clock from a timer interrupt, as well as for tests and replays. Since the clock uses interior mutability, a game can hold a reference to it while the frontend advances it.
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Clock, ManualClock};
+/// # use core::time::Duration;
+/// #
+/// let clock = ManualClock::new();
+/// clock.advance(Duration::from_millis(1500));
+/// assert_eq!(clock.now(), Duration::from_millis(1500));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ManualClock {
+    time: Cell<Duration>,
+}
+impl ManualClock {
+    /// Creates a clock which reports zero until advanced.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {time: Cell::new(Duration::from_secs(0))}
+    }
+    /// Advances the clock by the specified amount of time.
+    #[inline]
+    pub fn advance(&self, by: Duration) {
+        self.time.set(self.time.get() + by);
+    }
+    /// Sets the time reported by the clock.
+    ///
+    /// Setting it to a time earlier than the current one violates the contract of [`Clock`][clock] and will make the elapsed time of a game saturate to zero.
+    ///
+    /// [clock]: trait.Clock.html "Clock — a source of time for a game session"
+    #[inline]
+    pub fn set(&self, time: Duration) {
+        self.time.set(time);
+    }
+}
+impl Clock for ManualClock {
+    #[inline(always)]
+    fn now(&self) -> Duration {
+        self.time.get()
+    }
+}
+
+/// The phase of a [game session][game].
+///
+/// [game]: struct.Game.html "Game — a Minesweeper game session"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub enum GamePhase {
+    /// No tiles have been opened yet. The timer isn't running, and the first click is guaranteed not to hit a mine.
+    NotStarted,
+    /// At least one tile has been opened and the game isn't over yet.
+    Playing,
+    /// All tiles without mines have been opened.
+    Won,
+    /// A mine has been opened.
+    Lost {
+        /// The location of the mine which was opened. If a chord opened several mines at once, this is the first of them in the order of [`ChordOutcome`][co].
+        ///
+        /// [co]: type.ChordOutcome.html "ChordOutcome — the outcome of a chord operation"
+        exploded: FieldCoordinates,
+    },
+}
+impl GamePhase {
+    /// Returns `true` if the game has been won or lost, `false` otherwise.
+    #[inline]
+    pub const fn is_finished(self) -> bool {
+        match self {
+            Self::Won | Self::Lost {..} => true,
+            Self::NotStarted | Self::Playing => false,
+        }
+    }
+}
+impl Default for GamePhase {
+    /// Returns the `NotStarted` variant.
+    #[inline(always)]
+    fn default() -> Self {
+        Self::NotStarted
+    }
+}
+
+/// A Minesweeper game session.
+///
+/// While a [`Field`][field] only provides the building blocks, a game session wraps one and implements the rules on top of it: it keeps track of the [phase][phase] of the game, protects the first click from hitting a mine by relocating it the same way [`open_first`][m_of] does, opens clearings and chords, counts the flags and detects winning and losing after every action. Every action is recorded in a [journal][journal], which allows undoing and redoing it. The time is taken from a [`Clock`][clock] of the frontend's choice, which makes the session usable without the standard library.
+///
+/// Once the game is finished, all further actions are ignored.
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Field, Game, GamePhase, ManualClock, TileState, Flag, ClickOutcome};
+/// # use core::{num::NonZeroUsize, time::Duration};
+/// #
+/// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(4).unwrap(), NonZeroUsize::new(3).unwrap()]);
+/// field[[0, 0]].state = TileState::Mine(Flag::NotFlagged);
+/// field[[3, 2]].state = TileState::Mine(Flag::NotFlagged);
+///
+/// let clock = ManualClock::new();
+/// let mut game = Game::new(field, &clock);
+/// assert_eq!(game.phase(), GamePhase::NotStarted);
+///
+/// // The first click starts the timer and opens the clearing around it.
+/// clock.advance(Duration::from_secs(2));
+/// assert_eq!(game.open([3, 0]), Some(ClickOutcome::OpenClearing));
+/// assert_eq!(game.phase(), GamePhase::Playing);
+/// game.toggle_flag([0, 0]);
+/// assert_eq!(game.mines_left(), 1);
+///
+/// clock.advance(Duration::from_secs(5));
+/// game.open([0, 2]);
+/// assert_eq!(game.phase(), GamePhase::Won);
+/// assert_eq!(game.elapsed(), Duration::from_secs(5));
+/// ```
+///
+/// [field]: struct.Field.html "Field — a playfield"
+/// [phase]: enum.GamePhase.html "GamePhase — the phase of a game session"
+/// [m_of]: struct.Field.html#method.open_first "open_first — opens the specified tile as the first click of a game, relocating the mine on it if there is one"
+/// [clock]: trait.Clock.html "Clock — a source of time for a game session"
+/// [journal]: struct.Journal.html "Journal — a history of player actions which can be undone and redone"
+#[derive(Clone, Debug)]
+pub struct Game<Ct, Cf, C> {
+    field: Field<Ct, Cf>,
+    clock: C,
+    phase: GamePhase,
+    total_mines: usize,
+    flags: usize,
+    tiles_to_open: usize,
+    started_at: Option<Duration>,
+    finished_at: Option<Duration>,
+    journal: Journal<Cf>,
+    /// The phases before and after each entry of the journal.
+    phases: Vec<[GamePhase; 2]>,
+    /// The changes made by the action which is currently being performed.
+    changes: Vec<TileChange<Cf>>,
+    replay: Option<Replay<Cf>>,
+    created_at: Duration,
+}
+impl<Ct, Cf, C: Clock> Game<Ct, Cf, C> {
+    /// Starts a game session on the specified field, taking the time from the specified clock.
+    ///
+    /// If the field already has open tiles, the game is considered to be resumed: the phase is `Playing` (or `Won`, if there's nothing left to open) and the timer starts from zero right away. A [replay][replay] is only recorded if the field has neither open tiles nor flags.
+    ///
+    /// [replay]: struct.Replay.html "Replay — a recording of a game"
+    #[must_use = "traversing the entire field is obscenely expensive"]
+    pub fn new(field: Field<Ct, Cf>, clock: C) -> Self {
+        let total_mines = field.all_tiles()
+            .filter(|tile| tile.state.is_mine())
+            .count();
+        let flags = field.all_tiles()
+            .filter(|tile| tile.state.is_flagged())
+            .count();
+        let tiles_to_open = field.tiles_to_open();
+        let (phase, started_at) = if field.all_tiles().any(|tile| tile.state.is_open()) {
+            let phase = if tiles_to_open == 0 {GamePhase::Won} else {GamePhase::Playing};
+            (phase, Some(clock.now()))
+        } else {
+            (GamePhase::NotStarted, None)
+        };
+        let finished_at = if phase.is_finished() {started_at} else {None};
+        let replay = Replay::new(&field);
+        let created_at = clock.now();
+        Self {
+            field, clock, phase,
+            total_mines, flags, tiles_to_open,
+            started_at, finished_at,
+            journal: Journal::new(), phases: Vec::new(), changes: Vec::new(),
+            replay, created_at,
+        }
+    }
+}
+impl<Ct, Cf, C> Game<Ct, Cf, C> {
+    /// Returns a reference to the field the game is played on.
+    #[inline(always)]
+    pub const fn field(&self) -> &Field<Ct, Cf> {
+        &self.field
+    }
+    /// Ends the session and returns the field the game was played on.
+    #[inline(always)]
+    pub fn into_field(self) -> Field<Ct, Cf> {
+        self.field
+    }
+    /// Returns a reference to the clock used by the game.
+    #[inline(always)]
+    pub const fn clock(&self) -> &C {
+        &self.clock
+    }
+    /// Returns the current phase of the game.
+    #[inline(always)]
+    pub const fn phase(&self) -> GamePhase {
+        self.phase
+    }
+    /// Returns the total amount of mines on the field.
+    #[inline(always)]
+    pub const fn total_mines(&self) -> usize {
+        self.total_mines
+    }
+    /// Returns the amount of tiles marked with `Flag::Flagged`. Custom flags are not counted.
+    #[inline(always)]
+    pub const fn flags(&self) -> usize {
+        self.flags
+    }
+    /// Returns the amount of mines minus the amount of flags, which is what the mine counter of the classic game displays. Can be negative if the player placed more flags than there are mines.
+    #[inline]
+    #[allow(clippy::cast_possible_wrap)] // Fields with more than isize::MAX tiles can't be allocated anyway.
+    pub const fn mines_left(&self) -> isize {
+        self.total_mines as isize - self.flags as isize
+    }
+    /// Returns the amount of tiles which the player still needs to open in order to win the game.
+    #[inline(always)]
+    pub const fn tiles_to_open(&self) -> usize {
+        self.tiles_to_open
+    }
+    /// Returns the history of the actions performed in the game, which are used by [`undo`][m_undo] and [`redo`][m_redo].
+    ///
+    /// [m_undo]: #method.undo "undo — undoes the newest action in the journal"
+    /// [m_redo]: #method.redo "redo — redoes the oldest undone action in the journal"
+    #[inline(always)]
+    pub const fn journal(&self) -> &Journal<Cf> {
+        &self.journal
+    }
+    /// Returns the recording of the game, or `None` if the game was started on a field with open tiles or flags.
+    #[inline(always)]
+    pub const fn replay(&self) -> Option<&Replay<Cf>> {
+        self.replay.as_ref()
+    }
+}
+impl<Ct, Cf, C: Clock> Game<Ct, Cf, C> {
+    /// Returns the time spent on the game: zero before the first click, the time since the first click while playing, or the total time of the game once it's finished.
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        match (self.started_at, self.finished_at) {
+            (Some(start), Some(end)) => end.checked_sub(start),
+            (Some(start), None) => self.clock.now().checked_sub(start),
+            (None, _) => None,
+        }.unwrap_or_default()
+    }
+}
+impl<Ct, Cf: Clone, C: Clock> Game<Ct, Cf, C> {
+    /// Opens the specified tile and returns the outcome of clicking it, or `None` if the index is out of bounds.
+    ///
+    /// Unlike [`Field::open`][m_open], this opens the entire clearing (including its shore) if the tile doesn't have neighboring mines. If this is the first click of the game, the mine on the tile is relocated the same way [`open_first`][m_of] does. Flagged, open and void tiles are not affected, and neither is anything after the game is finished — `Nothing` is returned in those cases. Chords are performed by [`chord`][m_chord] instead.
+    ///
+    /// [m_open]: struct.Field.html#method.open "open — opens exactly one tile and returns the outcome of clicking it"
+    /// [m_of]: struct.Field.html#method.open_first "open_first — opens the specified tile as the first click of a game, relocating the mine on it if there is one"
+    /// [m_chord]: #method.chord "chord — performs a chord on the specified tile"
+    pub fn open(&mut self, coordinates: FieldCoordinates) -> Option<ClickOutcome> {
+        if !self.field.contains(coordinates) {return None}
+        if self.phase.is_finished() || self.field[coordinates].state.is_flagged() || self.field[coordinates].state.is_void() {
+            self.record(ReplayAction::Open {location: coordinates, outcome: ClickOutcome::Nothing});
+            return Some(ClickOutcome::Nothing);
+        }
+        let phase_before = self.phase;
+        if self.phase == GamePhase::NotStarted {
+            grid::relocate_mine(&mut self.recorder(), coordinates);
+            self.phase = GamePhase::Playing;
+            self.started_at = Some(self.clock.now());
+        }
+        let outcome = self.reveal(coordinates);
+        self.update_phase(coordinates, outcome);
+        self.commit(Action::Open(coordinates), phase_before);
+        self.record(ReplayAction::Open {location: coordinates, outcome});
+        Some(outcome)
+    }
+    /// Performs a chord on the specified tile and returns the outcomes of opening each of its neighbors, or `None` if the index is out of bounds.
+    ///
+    /// If the tile is a number and exactly as many flags as the number surround it, all unflagged closed neighbors are opened as if by [`open`][m_open], which loses the game if the flags were placed incorrectly. Otherwise, or if the game is finished, nothing happens and all outcomes are `Nothing`.
+    ///
+    /// [m_open]: #method.open "open — opens the specified tile and the clearing around it"
+    pub fn chord(&mut self, coordinates: FieldCoordinates) -> Option<ChordOutcome> {
+        if !self.field.contains(coordinates) {return None}
+        let phase_before = self.phase;
+        let outcome = self.chord_once(coordinates);
+        self.commit(Action::Chord(coordinates), phase_before);
+        self.record(ReplayAction::Chord {location: coordinates, outcome: outcome.clone()});
+        Some(outcome)
+    }
+    /// Performs a chord on the specified tile, then on every number tile opened by it, and so on, and returns the outcomes of the chords which opened anything, or `None` if the index is out of bounds.
+    ///
+    /// The whole operation is recorded as a single action in the [journal][m_journal].
+    ///
+    /// [m_journal]: #method.journal "journal — returns the history of the actions performed in the game"
+    pub fn recursive_chord(&mut self, coordinates: FieldCoordinates) -> Option<Vec<RecursiveChordOutcome>> {
+        if !self.field.contains(coordinates) {return None}
+        let phase_before = self.phase;
+        let mut chord_outcomes = Vec::new();
+        let mut stack = alloc::vec![coordinates];
+        while let Some(location) = stack.pop() {
+            let outcome = self.chord_once(location);
+            if outcome.iter().all(|&click| click == ClickOutcome::Nothing) {continue}
+            let (dimensions, topology, neighborhood) = (self.field.dimensions(), self.field.topology(), self.field.neighborhood());
+            for (index, click) in outcome.iter().enumerate() {
+                if let (Some(number_tile), ClickOutcome::OpenNumber(_)) = (neighbor(dimensions, topology, neighborhood, location, index), click) {
+                    stack.push(number_tile);
+                }
+            }
+            chord_outcomes.push((location, outcome));
+        }
+        self.commit(Action::RecursiveChord(coordinates), phase_before);
+        if self.replay.is_some() {
+            self.record(ReplayAction::RecursiveChord {location: coordinates, outcome: chord_outcomes.clone()});
+        }
+        Some(chord_outcomes)
+    }
+    /// Sets the flag on the specified tile and returns the flag which was there before, or `None` if the index is out of bounds, the tile is open or void or the game is finished.
+    pub fn set_flag(&mut self, coordinates: FieldCoordinates, flag: Flag<Cf>) -> Option<Flag<Cf>> {
+        if !self.field.contains(coordinates) || self.phase.is_finished() {return None}
+        let (new_state, old_flag) = match self.field[coordinates].state.clone() {
+            TileState::ClosedEmpty(old_flag) => (TileState::ClosedEmpty(flag), old_flag),
+            TileState::Mine(old_flag) => (TileState::Mine(flag), old_flag),
+            TileState::OpenEmpty
+          | TileState::OpenNumber(_)
+          | TileState::Void => return None,
+        };
+        if let (Some(_), Some(flag)) = (&self.replay, new_state.flag_state()) {
+            let (flag, previous) = (flag.clone(), old_flag.clone());
+            self.record(ReplayAction::SetFlag {location: coordinates, flag, previous});
+        }
+        self.recorder().set_state(coordinates, new_state);
+        self.commit(Action::SetFlag(coordinates), self.phase);
+        Some(old_flag)
+    }
+    /// Toggles a regular flag on the specified tile and returns `true` if the tile is flagged afterwards, or `None` if the index is out of bounds, the tile is open or void or the game is finished.
+    ///
+    /// Custom flags are replaced with a regular one. Void tiles are holes in the field, so they can never be flagged and are left as they are.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{Field, Game, ManualClock, TileState, Flag};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(1).unwrap()]);
+    /// field[[0, 0]].state = TileState::Mine(Flag::NotFlagged);
+    /// field[[2, 0]].state = TileState::Void;
+    ///
+    /// let mut game = Game::new(field, ManualClock::new());
+    /// assert_eq!(game.toggle_flag([0, 0]), Some(true));
+    /// assert_eq!(game.toggle_flag([0, 0]), Some(false));
+    /// // The hole in the field can't be flagged.
+    /// assert_eq!(game.toggle_flag([2, 0]), None);
+    /// assert!(game.field()[[2, 0]].state.is_void());
+    /// assert_eq!(game.flags(), 0);
+    /// ```
+    pub fn toggle_flag(&mut self, coordinates: FieldCoordinates) -> Option<bool> {
+        if !self.field.contains(coordinates) {return None}
+        let flagged = self.field[coordinates].state.is_flagged();
+        let new_flag = if flagged {Flag::NotFlagged} else {Flag::Flagged};
+        self.set_flag(coordinates, new_flag).map(|_| !flagged)
+    }
+    /// Undoes the newest action in the [journal][m_journal] and returns it, or returns `None` if there's nothing to undo.
+    ///
+    /// This restores the tiles changed by the action as well as the phase of the game, which means that a game lost by opening a mine continues as if the mine was never clicked. The timer keeps running from the first click, unless the first click itself is undone.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{Field, Game, GamePhase, ManualClock, TileState, Flag, Action};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// // A row of four tiles with a mine on each end.
+    /// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(4).unwrap(), NonZeroUsize::new(1).unwrap()]);
+    /// field[[0, 0]].state = TileState::Mine(Flag::NotFlagged);
+    /// field[[3, 0]].state = TileState::Mine(Flag::NotFlagged);
+    ///
+    /// let mut game = Game::new(field, ManualClock::new());
+    /// game.open([1, 0]);
+    /// game.open([0, 0]);
+    /// assert_eq!(game.phase(), GamePhase::Lost {exploded: [0, 0]});
+    /// // Take the explosive click back.
+    /// assert_eq!(game.undo(), Some(Action::Open([0, 0])));
+    /// assert_eq!(game.phase(), GamePhase::Playing);
+    /// game.open([2, 0]);
+    /// assert_eq!(game.phase(), GamePhase::Won);
+    /// ```
+    ///
+    /// [m_journal]: #method.journal "journal — returns the history of the actions performed in the game"
+    pub fn undo(&mut self) -> Option<Action> {
+        let entry = self.journal.undo(&mut self.field)?;
+        for change in entry.changes.iter().rev() {
+            update_counters(&mut self.flags, &mut self.tiles_to_open, &change.after, &change.before);
+        }
+        let action = entry.action;
+        let [phase_before, _] = self.phases[self.journal.position()];
+        self.restore_phase(phase_before);
+        self.record(ReplayAction::Undo(action));
+        Some(action)
+    }
+    /// Redoes the oldest undone action in the [journal][m_journal] and returns it, or returns `None` if there's nothing to redo.
+    ///
+    /// [m_journal]: #method.journal "journal — returns the history of the actions performed in the game"
+    pub fn redo(&mut self) -> Option<Action> {
+        let entry = self.journal.redo(&mut self.field)?;
+        for change in &entry.changes {
+            update_counters(&mut self.flags, &mut self.tiles_to_open, &change.before, &change.after);
+        }
+        let action = entry.action;
+        let [_, phase_after] = self.phases[self.journal.position() - 1];
+        self.restore_phase(phase_after);
+        self.record(ReplayAction::Redo(action));
+        Some(action)
+    }
+    /// Forgets all actions in the [journal][m_journal], making the current state of the game the start of the history.
+    ///
+    /// The journal grows with every action, so long sessions which don't need undo can use this to free memory.
+    ///
+    /// [m_journal]: #method.journal "journal — returns the history of the actions performed in the game"
+    #[inline]
+    pub fn clear_journal(&mut self) {
+        self.journal.clear();
+        self.phases.clear();
+        self.record(ReplayAction::ClearJournal);
+    }
+
+    /// Performs a chord without recording it in the journal.
+    fn chord_once(&mut self, coordinates: FieldCoordinates) -> ChordOutcome {
+        let mut result = alloc::vec![ClickOutcome::Nothing; self.field.neighborhood().len()];
+        if self.phase.is_finished() || grid::chord(&mut self.recorder(), coordinates, &mut result).is_err() {
+            return result;
+        }
+        // The chord opens every neighbor on its own, so the clearings are opened afterwards, before deciding whether the game is over.
+        let (dimensions, topology, neighborhood) = (self.field.dimensions(), self.field.topology(), self.field.neighborhood());
+        let opened = result.iter().enumerate()
+            .filter(|(_, &click)| click != ClickOutcome::Nothing)
+            .filter_map(|(index, &click)| Some((neighbor(dimensions, topology, neighborhood, coordinates, index)?, click)))
+            .collect::<Vec<_>>();
+        for &(location, click) in &opened {
+            if click == ClickOutcome::OpenClearing {
+                grid::open_clearing(&mut self.recorder(), location, true, true);
+            }
+        }
+        for (location, click) in opened {
+            self.update_phase(location, click);
+        }
+        result
+    }
+    /// Finishes the game if the outcome of opening the specified tile lost it or there's nothing left to open.
+    fn update_phase(&mut self, coordinates: FieldCoordinates, outcome: ClickOutcome) {
+        if self.phase.is_finished() {return}
+        if outcome == ClickOutcome::Explosion {
+            self.phase = GamePhase::Lost {exploded: coordinates};
+        } else if self.tiles_to_open == 0 {
+            self.phase = GamePhase::Won;
+        } else {return}
+        self.finished_at = Some(self.clock.now());
+    }
+    /// Sets the phase of the game after undoing or redoing an action, starting or stopping the timer accordingly.
+    fn restore_phase(&mut self, phase: GamePhase) {
+        self.phase = phase;
+        if phase == GamePhase::NotStarted {
+            self.started_at = None;
+        } else if self.started_at.is_none() {
+            self.started_at = Some(self.clock.now());
+        }
+        self.finished_at = if phase.is_finished() {Some(self.clock.now())} else {None};
+    }
+    /// Adds an event to the replay, if it's being recorded.
+    fn record(&mut self, action: ReplayAction<Cf>) {
+        let time = self.clock.now().checked_sub(self.created_at).unwrap_or_default();
+        if let Some(replay) = &mut self.replay {
+            replay.events.push(ReplayEvent {time, action, phase: self.phase});
+        }
+    }
+    /// Records the changes made since the last action as a journal entry, unless the action didn't do anything.
+    fn commit(&mut self, action: Action, phase_before: GamePhase) {
+        if self.changes.is_empty() && self.phase == phase_before {return}
+        self.phases.truncate(self.journal.position());
+        self.phases.push([phase_before, self.phase]);
+        self.journal.record(JournalEntry {action, changes: mem::take(&mut self.changes)});
+    }
+}
+impl<Ct, Cf: Clone, C> Game<Ct, Cf, C> {
+    /// Returns a view of the field which updates the counters and remembers the changes for the journal, through which all tiles are changed.
+    fn recorder(&mut self) -> Recorder<'_, Ct, Cf> {
+        Recorder {
+            field: &mut self.field,
+            flags: &mut self.flags,
+            tiles_to_open: &mut self.tiles_to_open,
+            changes: &mut self.changes,
+        }
+    }
+    /// Opens a closed tile, along with the clearing around it (including its shore) if it has no neighboring mines, and returns the outcome of clicking it. Flagged tiles in the clearing are kept closed.
+    fn reveal(&mut self, coordinates: FieldCoordinates) -> ClickOutcome {
+        match self.field[coordinates].state {
+            TileState::ClosedEmpty(_) | TileState::Mine(_) => {},
+            TileState::OpenEmpty
+          | TileState::OpenNumber(_)
+          | TileState::Void => return ClickOutcome::Nothing,
+        }
+        let mut field = self.recorder();
+        let outcome = grid::open(&mut field, coordinates).unwrap_or_default();
+        if outcome == ClickOutcome::OpenClearing {
+            grid::open_clearing(&mut field, coordinates, true, true);
+        }
+        outcome
+    }
+}
+
+/// A view of the field of a game through which the shared grid logic changes its tiles, keeping the counters of the game up to date and remembering every change for the journal.
+struct Recorder<'g, Ct, Cf> {
+    field: &'g mut Field<Ct, Cf>,
+    flags: &'g mut usize,
+    tiles_to_open: &'g mut usize,
+    changes: &'g mut Vec<TileChange<Cf>>,
+}
+impl<Ct, Cf: Clone> Grid for Recorder<'_, Ct, Cf> {
+    type Location = FieldCoordinates;
+    type Payload = Ct;
+    type Flag = Cf;
+    #[inline(always)]
+    fn area(&self) -> usize {
+        self.field.area()
+    }
+    #[inline(always)]
+    fn index_of(&self, location: FieldCoordinates) -> usize {
+        self.field.index_of(location)
+    }
+    #[inline(always)]
+    fn location_of(&self, index: usize) -> FieldCoordinates {
+        self.field.location_of(index)
+    }
+    #[inline(always)]
+    fn neighbor_count(&self) -> usize {
+        self.field.neighbor_count()
+    }
+    #[inline(always)]
+    fn neighbor(&self, center: FieldCoordinates, direction: usize) -> Option<FieldCoordinates> {
+        Grid::neighbor(&*self.field, center, direction)
+    }
+    #[inline(always)]
+    fn tile(&self, location: FieldCoordinates) -> Option<&Tile<Ct, Cf>> {
+        self.field.get(location)
+    }
+    fn update_state<F>(&mut self, location: FieldCoordinates, update: F)
+    where F: FnOnce(TileState<Cf>) -> TileState<Cf> {
+        if let Some(tile) = self.field.get_mut(location) {
+            let before = mem::take(&mut tile.state);
+            tile.state = update(before.clone());
+            update_counters(self.flags, self.tiles_to_open, &before, &tile.state);
+            self.changes.push(TileChange {location, before, after: tile.state.clone()});
+        }
+    }
+}
+
+/// Updates the flag and tiles-to-open counters of a game after a tile changes its state.
+fn update_counters<Cf>(flags: &mut usize, tiles_to_open: &mut usize, before: &TileState<Cf>, after: &TileState<Cf>) {
+    if before.is_flagged() {*flags -= 1}
+    if after.is_flagged() {*flags += 1}
+    if before.is_required_to_open() {*tiles_to_open -= 1}
+    if after.is_required_to_open() {*tiles_to_open += 1}
+}
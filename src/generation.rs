@@ -0,0 +1,452 @@
+//! Random field generation.
+//!
+//! Every generation method is available in two flavors: one which takes the random number generator as an argument and one which uses a built-in generator. The former works with any [`Rng`][rng] implementation, including ones available in `no_std` environments, while the latter is either seeded explicitly (and thus produces the exact same mine layout for the same seed and dimensions on every platform) or uses `thread_rng` from `rand`, which requires the `std` feature.
+//!
+//! [rng]: https://docs.rs/rand/0.7/rand/trait.Rng.html "Rng — an automatically-implemented extension trait on RngCore"
+
+use core::{
+    fmt::{self, Formatter},
+    num::NonZeroUsize,
+};
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+use crate::{
+    Field, FieldDimensions, FieldCoordinates,
+    TileState, Error,
+    solver::{Board, Cell},
+    grid,
+};
+
+impl<Ct, Cf> Field<Ct, Cf> {
+    /// Adds mines with the selected percentage of mines while keeping the specified [safe zone][sz] free of mines.
+    ///
+    /// The thread-local random number generator from `rand` is used. See [`populate_with_rng`][m_pwr] for a version which accepts a custom random number generator and [`populate_from_seed`][m_pfs] for a reproducible one.
+    ///
+    /// # Panics
+    /// See [`populate_with_rng`][m_pwr].
+    ///
+    /// [m_pwr]: #method.populate_with_rng "populate_with_rng — adds mines using the specified random number generator"
+    /// [m_pfs]: #method.populate_from_seed "populate_from_seed — adds mines using a random number generator with the specified seed"
+    /// [sz]: enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn populate<'a>(&mut self, mine_percentage: f64, safe_zone: impl Into<SafeZone<'a>>) {
+        self.populate_with_rng(mine_percentage, safe_zone, &mut rand::thread_rng());
+    }
+    /// Adds mines with the selected percentage of mines while keeping the specified [safe zone][sz] free of mines. The specified random number generator is used to choose the locations of the mines.
+    ///
+    /// The percentage is taken of the tiles which aren't void and converted to the amount of mines by rounding to the nearest integer, after which the mines are placed using [`populate_exact_with_rng`][m_pewr].
+    ///
+    /// # Panics
+    /// Panics if the percentage of mines is not positive or if there's not enough space on the field for the resulting amount of mines. See [`try_populate_with_rng`][m_tpwr] for a version which returns an error instead.
+    ///
+    /// [m_pewr]: #method.populate_exact_with_rng "populate_exact_with_rng — adds exactly the specified amount of mines using the specified random number generator"
+    /// [m_tpwr]: #method.try_populate_with_rng "try_populate_with_rng — adds mines using the specified random number generator, or returns an error if that's impossible"
+    /// [sz]: enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+    pub fn populate_with_rng<'a, R>(&mut self, mine_percentage: f64, safe_zone: impl Into<SafeZone<'a>>, rng: &mut R)
+    where R: Rng + ?Sized {
+        if let Err(e) = self.try_populate_with_rng(mine_percentage, safe_zone, rng) {
+            panic!("{}", e);
+        }
+    }
+    /// Adds mines with the selected percentage of mines while keeping the specified [safe zone][sz] free of mines. The mine layout is fully determined by the seed.
+    ///
+    /// The same seed, field dimensions, percentage and safe zone are guaranteed to produce the same mine layout on every platform, including `no_std` ones, which makes this suitable for reproducible tests and shared "daily challenge" boards.
+    ///
+    /// [sz]: enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+    #[inline]
+    pub fn populate_from_seed<'a>(&mut self, mine_percentage: f64, safe_zone: impl Into<SafeZone<'a>>, seed: u64) {
+        self.populate_with_rng(mine_percentage, safe_zone, &mut Pcg64::seed_from_u64(seed));
+    }
+
+    /// Adds mines with the selected percentage of mines while keeping the specified [safe zone][sz] free of mines, or returns an error if that's impossible.
+    ///
+    /// This is the fallible version of [`populate`][m_populate], which uses the thread-local random number generator from `rand`.
+    ///
+    /// # Errors
+    /// See [`try_populate_with_rng`][m_tpwr].
+    ///
+    /// [m_populate]: #method.populate "populate — adds mines with the selected percentage of mines"
+    /// [m_tpwr]: #method.try_populate_with_rng "try_populate_with_rng — adds mines using the specified random number generator, or returns an error if that's impossible"
+    /// [sz]: enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn try_populate<'a>(&mut self, mine_percentage: f64, safe_zone: impl Into<SafeZone<'a>>) -> Result<(), Error> {
+        self.try_populate_with_rng(mine_percentage, safe_zone, &mut rand::thread_rng())
+    }
+    /// Adds mines with the selected percentage of mines while keeping the specified [safe zone][sz] free of mines, or returns an error if that's impossible. The specified random number generator is used to choose the locations of the mines.
+    ///
+    /// This is the fallible version of [`populate_with_rng`][m_pwr].
+    ///
+    /// # Errors
+    /// Returns [`InvalidMinePercentage`][e_imp] if the percentage of mines is not positive, [`TooManyMines`][e_tmm] if there's not enough space on the field for the resulting amount of mines and [`OutOfBounds`][e_oob] if the safe zone includes a tile outside the field.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{Field, SafeZone, Error};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(3).unwrap(); 2]);
+    /// assert_eq!(field.try_populate_from_seed(-0.5, SafeZone::None, 1), Err(Error::InvalidMinePercentage));
+    /// assert_eq!(
+    ///     field.try_populate_from_seed(1.0, SafeZone::Tile([1, 1]), 1),
+    ///     Err(Error::TooManyMines {requested: 9, available: 8}),
+    /// );
+    /// field.try_populate_from_seed(0.5, SafeZone::Tile([1, 1]), 1)?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    ///
+    /// [m_pwr]: #method.populate_with_rng "populate_with_rng — adds mines using the specified random number generator"
+    /// [e_imp]: enum.Error.html#variant.InvalidMinePercentage "Error::InvalidMinePercentage — the percentage of mines is not a positive number"
+    /// [e_tmm]: enum.Error.html#variant.TooManyMines "Error::TooManyMines — the requested amount of mines does not fit on the field"
+    /// [e_oob]: enum.Error.html#variant.OutOfBounds "Error::OutOfBounds — the specified location is outside the field"
+    /// [sz]: enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+    #[allow(
+        clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss,
+        clippy::suboptimal_flops, // mul_add is not available without std.
+    )]
+    pub fn try_populate_with_rng<'a, R>(&mut self, mine_percentage: f64, safe_zone: impl Into<SafeZone<'a>>, rng: &mut R) -> Result<(), Error>
+    where R: Rng + ?Sized {
+        if mine_percentage.is_nan() || mine_percentage <= 0.0 {
+            return Err(Error::InvalidMinePercentage);
+        }
+        // Holes in the field don't count towards its area.
+        let area = self.all_tiles().filter(|tile| !tile.state.is_void()).count();
+        // Round to the nearest integer by hand, since f64::round is not available without std.
+        let num_mines: usize = (area as f64 * mine_percentage + 0.5) as usize; // The number of mines is usize because the area is usize.
+        self.populate_exact_with_rng(num_mines, safe_zone, rng)?;
+        Ok(())
+    }
+    /// Adds mines with the selected percentage of mines while keeping the specified [safe zone][sz] free of mines, or returns an error if that's impossible. The mine layout is fully determined by the seed.
+    ///
+    /// This is the fallible version of [`populate_from_seed`][m_pfs].
+    ///
+    /// # Errors
+    /// See [`try_populate_with_rng`][m_tpwr].
+    ///
+    /// [m_pfs]: #method.populate_from_seed "populate_from_seed — adds mines using a random number generator with the specified seed"
+    /// [m_tpwr]: #method.try_populate_with_rng "try_populate_with_rng — adds mines using the specified random number generator, or returns an error if that's impossible"
+    /// [sz]: enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+    #[inline]
+    pub fn try_populate_from_seed<'a>(&mut self, mine_percentage: f64, safe_zone: impl Into<SafeZone<'a>>, seed: u64) -> Result<(), Error> {
+        self.try_populate_with_rng(mine_percentage, safe_zone, &mut Pcg64::seed_from_u64(seed))
+    }
+
+    /// Adds exactly the specified amount of mines while keeping the specified [safe zone][sz] free of mines.
+    ///
+    /// The thread-local random number generator from `rand` is used. See [`populate_exact_with_rng`][m_pewr] for a version which accepts a custom random number generator and [`populate_exact_from_seed`][m_pefs] for a reproducible one.
+    ///
+    /// # Errors
+    /// See [`populate_exact_with_rng`][m_pewr].
+    ///
+    /// [m_pewr]: #method.populate_exact_with_rng "populate_exact_with_rng — adds exactly the specified amount of mines using the specified random number generator"
+    /// [m_pefs]: #method.populate_exact_from_seed "populate_exact_from_seed — adds exactly the specified amount of mines using a random number generator with the specified seed"
+    /// [sz]: enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn populate_exact<'a>(&mut self, num_mines: usize, safe_zone: impl Into<SafeZone<'a>>) -> Result<(), GenerationError> {
+        self.populate_exact_with_rng(num_mines, safe_zone, &mut rand::thread_rng())
+    }
+    /// Adds exactly the specified amount of mines while keeping the specified [safe zone][sz] free of mines. The specified random number generator is used to choose the locations of the mines.
+    ///
+    /// Mines are only placed on tiles which don't already contain one and aren't void, meaning that populating an empty field produces a field with exactly `num_mines` distinct mines. The locations are chosen using a partial [Fisher–Yates shuffle][fys] over the candidate tiles, which makes the running time independent of how dense the resulting field is. Just like with [`populate_with_rng`][m_pwr], only `u64` values are sampled from the generator, so the result doesn't depend on the pointer width.
+    ///
+    /// # Errors
+    /// Returns [`TooManyMines`][e_tmm] if there are less than `num_mines` tiles which can hold a new mine and [`SafeSpotOutOfBounds`][e_ssoob] if the safe zone includes a tile outside the field. The field is not modified in both cases.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{Field, GenerationError};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let beginner = [NonZeroUsize::new(9).unwrap(), NonZeroUsize::new(9).unwrap()];
+    /// let mut field = Field::<(), ()>::empty(beginner);
+    /// field.populate_exact_from_seed(10, Some([4, 4]), 1337)?;
+    /// assert_eq!(field.all_tiles().filter(|t| t.state.is_mine()).count(), 10);
+    /// assert!(!field[[4, 4]].state.is_mine());
+    ///
+    /// let mut tiny = Field::<(), ()>::empty([NonZeroUsize::new(2).unwrap(); 2]);
+    /// assert_eq!(
+    ///     tiny.populate_exact_from_seed(4, Some([0, 0]), 1337),
+    ///     Err(GenerationError::TooManyMines {requested: 4, available: 3}),
+    /// );
+    /// # Ok::<(), GenerationError>(())
+    /// ```
+    ///
+    /// [fys]: https://en.wikipedia.org/wiki/Fisher%E2%80%93Yates_shuffle "Fisher–Yates shuffle — Wikipedia"
+    /// [m_pwr]: #method.populate_with_rng "populate_with_rng — adds mines using the specified random number generator"
+    /// [e_tmm]: enum.GenerationError.html#variant.TooManyMines "TooManyMines — the requested amount of mines does not fit on the field"
+    /// [e_ssoob]: enum.GenerationError.html#variant.SafeSpotOutOfBounds "SafeSpotOutOfBounds — the safe zone includes a tile outside the field"
+    /// [sz]: enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+    pub fn populate_exact_with_rng<'a, R>(&mut self, num_mines: usize, safe_zone: impl Into<SafeZone<'a>>, rng: &mut R) -> Result<(), GenerationError>
+    where R: Rng + ?Sized {
+        grid::populate_exact(self, num_mines, safe_zone.into(), rng)
+    }
+    /// Adds exactly the specified amount of mines while keeping the specified [safe zone][sz] free of mines. The mine layout is fully determined by the seed.
+    ///
+    /// The reproducibility guarantees are the same as the ones for [`populate_from_seed`][m_pfs].
+    ///
+    /// # Errors
+    /// See [`populate_exact_with_rng`][m_pewr].
+    ///
+    /// [m_pfs]: #method.populate_from_seed "populate_from_seed — adds mines using a random number generator with the specified seed"
+    /// [m_pewr]: #method.populate_exact_with_rng "populate_exact_with_rng — adds exactly the specified amount of mines using the specified random number generator"
+    /// [sz]: enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+    #[inline]
+    pub fn populate_exact_from_seed<'a>(&mut self, num_mines: usize, safe_zone: impl Into<SafeZone<'a>>, seed: u64) -> Result<(), GenerationError> {
+        self.populate_exact_with_rng(num_mines, safe_zone, &mut Pcg64::seed_from_u64(seed))
+    }
+}
+impl<Ct, Cf> Field<Ct, Cf>
+where Ct: Default {
+    /// Creates a field with the given dimensions and populates it with mines using a random number generator with the specified seed.
+    ///
+    /// See [`populate_from_seed`][m_pfs] for the reproducibility guarantees.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::Field;
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let dimensions = [NonZeroUsize::new(16).unwrap(), NonZeroUsize::new(16).unwrap()];
+    /// let daily = Field::<(), ()>::from_seed(dimensions, 0.15, Some([8, 8]), 20_201_017);
+    /// let same_daily = Field::<(), ()>::from_seed(dimensions, 0.15, Some([8, 8]), 20_201_017);
+    /// assert_eq!(daily, same_daily); // Everyone gets the same board.
+    /// ```
+    ///
+    /// [m_pfs]: #method.populate_from_seed "populate_from_seed — adds mines using a random number generator with the specified seed"
+    #[must_use = "this performs a memory allocation as big as the area of the field"]
+    pub fn from_seed<'a>(dimensions: FieldDimensions, mine_percentage: f64, safe_zone: impl Into<SafeZone<'a>>, seed: u64) -> Self {
+        let mut field = Self::empty(dimensions);
+        field.populate_from_seed(mine_percentage, safe_zone, seed);
+        field
+    }
+
+    /// Generates a field with exactly the specified amount of mines which can be fully solved from the specified first click by pure deduction, i.e. without ever having to guess, and returns it along with the amount of attempts it took to generate it.
+    ///
+    /// The thread-local random number generator from `rand` is used. See [`no_guess_with_rng`][m_ngwr] for the details and a version which accepts a custom random number generator, and [`no_guess_from_seed`][m_ngfs] for a reproducible one.
+    ///
+    /// # Errors
+    /// See [`no_guess_with_rng`][m_ngwr].
+    ///
+    /// [m_ngwr]: #method.no_guess_with_rng "no_guess_with_rng — generates a field which can be solved without guessing using the specified random number generator"
+    /// [m_ngfs]: #method.no_guess_from_seed "no_guess_from_seed — generates a field which can be solved without guessing using a random number generator with the specified seed"
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn no_guess(dimensions: FieldDimensions, num_mines: usize, first_click: FieldCoordinates, max_attempts: NonZeroUsize) -> Result<(Self, NonZeroUsize), GenerationError> {
+        Self::no_guess_with_rng(dimensions, num_mines, first_click, max_attempts, &mut rand::thread_rng())
+    }
+    /// Generates a field with exactly the specified amount of mines which can be fully solved from the specified first click by pure deduction, i.e. without ever having to guess, and returns it along with the amount of attempts it took to generate it. The specified random number generator is used to choose the locations of the mines.
+    ///
+    /// Every attempt generates a random mine layout with the first click and its neighbors kept free of mines (the same [`SafeZone::Neighborhood`][sz_n] as the one accepted by [`populate_exact_with_rng`][m_pewr]), so that the first click always opens a clearing, and then plays the field from that click like a player would, only ever opening tiles and placing flags which can be proven to be safe or to contain a mine from the open numbers, the flags and the total amount of mines. If that opens all tiles without mines, the layout is accepted; otherwise, a new attempt is made, until `max_attempts` attempts have been made.
+    ///
+    /// The chance for a random layout to be solvable without guessing drops quickly as the density of mines grows, so the attempt budget should be tuned for the field sizes in use: the returned attempt count is intended to help with that.
+    ///
+    /// The field is always created with [`empty`][m_empty], which means that it has no void tiles and uses the `Bounded` [topology][topology] and the Moore [neighborhood][neighborhood]. The safe zone around the first click is therefore the 3x3 square around it, cut off by the edges of the field. Fields with other shapes and settings are not supported.
+    ///
+    /// # Errors
+    /// Returns [`TooManyMines`][e_tmm] and [`SafeSpotOutOfBounds`][e_ssoob] under the same conditions as [`populate_exact_with_rng`][m_pewr] does for the safe zone around the first click, and [`AttemptsExhausted`][e_ae] if none of the generated layouts could be solved without guessing.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{Field, GenerationError};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let beginner = [NonZeroUsize::new(9).unwrap(), NonZeroUsize::new(9).unwrap()];
+    /// let budget = NonZeroUsize::new(1000).unwrap();
+    /// let (field, attempts) = Field::<(), ()>::no_guess_from_seed(beginner, 10, [4, 4], budget, 2020)?;
+    /// assert_eq!(field.all_tiles().filter(|t| t.state.is_mine()).count(), 10);
+    /// assert!(attempts <= budget);
+    /// # Ok::<(), GenerationError>(())
+    /// ```
+    ///
+    /// [sz_n]: enum.SafeZone.html#variant.Neighborhood "SafeZone::Neighborhood — the specified tile and its neighbors"
+    /// [m_pewr]: #method.populate_exact_with_rng "populate_exact_with_rng — adds exactly the specified amount of mines using the specified random number generator"
+    /// [m_empty]: #method.empty "empty — creates an empty field filled with unopened tiles"
+    /// [topology]: enum.Topology.html "Topology — describes which edges of a field touch each other"
+    /// [neighborhood]: struct.Neighborhood.html "Neighborhood — describes which tiles around a tile count as its neighbors"
+    /// [e_tmm]: enum.GenerationError.html#variant.TooManyMines "TooManyMines — the requested amount of mines does not fit on the field"
+    /// [e_ssoob]: enum.GenerationError.html#variant.SafeSpotOutOfBounds "SafeSpotOutOfBounds — the safe zone includes a tile outside the field"
+    /// [e_ae]: enum.GenerationError.html#variant.AttemptsExhausted "AttemptsExhausted — none of the generated fields satisfied the requirements"
+    pub fn no_guess_with_rng<R>(
+        dimensions: FieldDimensions,
+        num_mines: usize,
+        first_click: FieldCoordinates,
+        max_attempts: NonZeroUsize,
+        rng: &mut R,
+    ) -> Result<(Self, NonZeroUsize), GenerationError>
+    where R: Rng + ?Sized {
+        let mut field = Self::empty(dimensions);
+        for attempt in 1..=max_attempts.get() {
+            if attempt > 1 {
+                // Start over from a clean field, reusing the allocation.
+                for tile in field.all_tiles_mut() {
+                    tile.state = TileState::default();
+                }
+            }
+            field.populate_exact_with_rng(num_mines, SafeZone::Neighborhood(first_click), rng)?;
+            if field.solvable_from(first_click) {
+                let attempts = NonZeroUsize::new(attempt)
+                    .unwrap_or_else(|| unreachable!("attempts are counted from 1"));
+                return Ok((field, attempts));
+            }
+        }
+        Err(GenerationError::AttemptsExhausted(max_attempts))
+    }
+    /// Generates a field with exactly the specified amount of mines which can be fully solved from the specified first click by pure deduction, i.e. without ever having to guess, and returns it along with the amount of attempts it took to generate it. The mine layout is fully determined by the seed.
+    ///
+    /// See [`no_guess_with_rng`][m_ngwr] for the details. The reproducibility guarantees are the same as the ones for [`populate_from_seed`][m_pfs].
+    ///
+    /// # Errors
+    /// See [`no_guess_with_rng`][m_ngwr].
+    ///
+    /// [m_ngwr]: #method.no_guess_with_rng "no_guess_with_rng — generates a field which can be solved without guessing using the specified random number generator"
+    /// [m_pfs]: #method.populate_from_seed "populate_from_seed — adds mines using a random number generator with the specified seed"
+    #[inline]
+    pub fn no_guess_from_seed(dimensions: FieldDimensions, num_mines: usize, first_click: FieldCoordinates, max_attempts: NonZeroUsize, seed: u64) -> Result<(Self, NonZeroUsize), GenerationError> {
+        Self::no_guess_with_rng(dimensions, num_mines, first_click, max_attempts, &mut Pcg64::seed_from_u64(seed))
+    }
+}
+
+impl<Ct, Cf> Field<Ct, Cf> {
+    /// Plays the field from the specified first click using only the deductions available to the player and returns whether that opens all tiles without mines.
+    fn solvable_from(&self, first_click: FieldCoordinates) -> bool {
+        let width = self.dimensions()[0].get();
+        let total_mines = self.all_tiles().filter(|tile| tile.state.is_mine()).count();
+        let mut board = Board::new(self.dimensions(), self.topology(), self.neighborhood());
+        for (cell, tile) in board.cells_mut().iter_mut().zip(self.all_tiles()) {
+            if tile.state.is_void() {*cell = Cell::Void}
+        }
+        let mut to_open = alloc::vec![first_click[0] + first_click[1] * width];
+        loop {
+            // Open the tiles proven safe, expanding clearings just like the player would.
+            while let Some(index) = to_open.pop() {
+                if board.cells()[index] != Cell::Closed {continue}
+                let count = self.count_neighboring_mines([index % width, index / width]);
+                board.cells_mut()[index] = Cell::Open(count);
+                if count == 0 {
+                    to_open.extend(board.neighbors(index));
+                }
+            }
+            let deductions = board.deduce(Some(total_mines));
+            if deductions.is_empty() {break}
+            for (index, is_mine, ..) in deductions {
+                if is_mine {
+                    board.cells_mut()[index] = Cell::Mine;
+                } else {
+                    to_open.push(index);
+                }
+            }
+        }
+        // Every closed tile which is left has to be a mine.
+        board.cells().iter().filter(|cell| !matches!(cell, Cell::Open(_) | Cell::Void)).count() == total_mines
+    }
+}
+
+/// The set of tiles which are kept free of mines during generation.
+///
+/// Typically, the safe zone is built around the tile which the player clicked first. Modern Minesweeper implementations guarantee that the first click opens a clearing, which is achieved using the `Neighborhood` variant, while classic ones only guarantee that the first click doesn't hit a mine, which corresponds to the `Tile` variant.
+///
+/// The type parameter is the type of the locations in the safe zone, which is [`CubeCoordinates`][cc] for a [`CubeField`][cube] and `FieldCoordinates` for the other fields. A location and an optional location can be converted into a safe zone, which makes it possible to pass `None` or `Some([x, y])` to the generation methods for the classic behavior.
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Field, SafeZone, ClickOutcome, GenerationError};
+/// # use core::num::NonZeroUsize;
+/// #
+/// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(9).unwrap(); 2]);
+/// field.populate_exact_from_seed(10, SafeZone::Neighborhood([4, 4]), 1337)?;
+/// // The first click is guaranteed to open a clearing.
+/// assert_eq!(field.open([4, 4]), Some(ClickOutcome::OpenClearing));
+/// # Ok::<(), GenerationError>(())
+/// ```
+///
+/// [cc]: cube/type.CubeCoordinates.html "CubeCoordinates — the coordinates of a tile on a three-dimensional field"
+/// [cube]: cube/struct.CubeField.html "CubeField — a three-dimensional playfield"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SafeZone<'a, L = FieldCoordinates> {
+    /// Mines can be placed anywhere on the field.
+    ///
+    /// Returned by the `Default` trait implementation.
+    None,
+    /// Only the specified tile is kept free of mines.
+    Tile(L),
+    /// The specified tile and all of its neighbors are kept free of mines, taking the topology and the neighborhood of the field into account. Tiles which would be outside the field are ignored.
+    Neighborhood(L),
+    /// An arbitrary set of tiles is kept free of mines.
+    Custom(&'a [L]),
+}
+impl<L> Default for SafeZone<'_, L> {
+    /// Returns the `None` variant.
+    #[inline(always)]
+    fn default() -> Self {
+        Self::None
+    }
+}
+impl<L> From<L> for SafeZone<'_, L> {
+    /// Returns the `Tile` variant with the specified coordinates.
+    #[inline(always)]
+    fn from(op: L) -> Self {
+        Self::Tile(op)
+    }
+}
+impl<L> From<Option<L>> for SafeZone<'_, L> {
+    /// Returns the `Tile` variant if a location is specified or the `None` variant otherwise.
+    #[inline(always)]
+    fn from(op: Option<L>) -> Self {
+        op.map_or(Self::None, Self::Tile)
+    }
+}
+impl<'a, L> From<&'a [L]> for SafeZone<'a, L> {
+    /// Returns the `Custom` variant with the specified tiles.
+    #[inline(always)]
+    fn from(op: &'a [L]) -> Self {
+        Self::Custom(op)
+    }
+}
+
+/// The error type returned by the generation methods which can fail.
+///
+/// Just like with [`SafeZone`][sz], the type parameter is the type of the reported locations.
+///
+/// [sz]: enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GenerationError<L = FieldCoordinates> {
+    /// There are less tiles which can hold a new mine than the amount of mines requested.
+    TooManyMines {
+        /// The amount of mines which was requested.
+        requested: usize,
+        /// The amount of tiles which could have received a mine.
+        available: usize,
+    },
+    /// The specified safe zone includes a tile outside the field.
+    SafeSpotOutOfBounds(L),
+    /// None of the generated fields satisfied the requirements within the specified amount of attempts.
+    AttemptsExhausted(NonZeroUsize),
+}
+impl<L: fmt::Debug> fmt::Display for GenerationError<L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyMines {requested, available} => write!(f,
+                "cannot place {requested} mines on a field with only {available} tiles available for them"
+            ),
+            Self::SafeSpotOutOfBounds(spot) => write!(f,
+                "the safe zone includes the out of bounds tile {spot:?}"
+            ),
+            Self::AttemptsExhausted(attempts) => write!(f,
+                "no suitable field was generated in {attempts} attempts"
+            ),
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl<L: fmt::Debug> std::error::Error for GenerationError<L> {}
+impl<L> From<GenerationError<L>> for Error<L> {
+    fn from(op: GenerationError<L>) -> Self {
+        match op {
+            GenerationError::TooManyMines {requested, available} => Self::TooManyMines {requested, available},
+            GenerationError::SafeSpotOutOfBounds(spot) => Self::OutOfBounds(spot),
+            GenerationError::AttemptsExhausted(attempts) => Self::AttemptsExhausted(attempts),
+        }
+    }
+}
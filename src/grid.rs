@@ -0,0 +1,261 @@
+//! The rules of the game which are shared by all shapes of fields.
+//!
+//! Regular, hexagonal and cubic fields only differ in how their tiles are located and which tiles are adjacent to each other. Everything else — counting mines, opening tiles, relocating the mine from the first click, chords, clearings, 3BV, validation and placing mines — is implemented here once, on top of the [`Grid`] trait.
+
+use core::num::{NonZeroUsize, NonZeroU8};
+use alloc::vec::Vec;
+#[cfg(feature = "generation")]
+use rand::Rng;
+use crate::{Tile, TileState, Flag, ClickOutcome, Error, InvalidFieldError};
+#[cfg(feature = "generation")]
+use crate::{SafeZone, GenerationError};
+
+/// The way a field lays out its tiles: how locations map to indices in row-major order and which locations are adjacent to each other.
+pub trait Grid {
+    /// The coordinates of a tile on the field.
+    type Location: Copy;
+    /// The custom payload of the tiles.
+    type Payload;
+    /// The custom flag type of the tiles.
+    type Flag;
+    /// Returns the total amount of tiles on the field.
+    fn area(&self) -> usize;
+    /// Returns the index of the specified location in row-major order. The location must be inside the field.
+    fn index_of(&self, location: Self::Location) -> usize;
+    /// Returns the location of the tile with the specified index in row-major order. The index must be less than the area of the field.
+    fn location_of(&self, index: usize) -> Self::Location;
+    /// Returns the amount of directions in which a tile can have a neighbor, which is also the length of the outcome of a chord.
+    fn neighbor_count(&self) -> usize;
+    /// Returns the location of the neighbor of the specified tile in the specified direction, or `None` if there isn't one inside the field.
+    fn neighbor(&self, center: Self::Location, direction: usize) -> Option<Self::Location>;
+    /// Returns the tile at the specified location, or `None` if the location is out of bounds.
+    fn tile(&self, location: Self::Location) -> Option<&Tile<Self::Payload, Self::Flag>>;
+    /// Replaces the state of the tile at the specified location with the result of the specified function, which receives the previous state. Does nothing if the location is out of bounds.
+    ///
+    /// All changes to the field made by the shared logic go through this method, which allows game sessions to record them.
+    fn update_state<F>(&mut self, location: Self::Location, update: F)
+    where F: FnOnce(TileState<Self::Flag>) -> TileState<Self::Flag>;
+    /// Sets the state of the tile at the specified location. Does nothing if the location is out of bounds.
+    #[inline]
+    fn set_state(&mut self, location: Self::Location, state: TileState<Self::Flag>) {
+        self.update_state(location, |_| state);
+    }
+}
+
+/// Returns an iterator over the locations of the neighbors of the specified tile which are inside the field, in the order of their directions.
+fn neighbors<G: Grid>(grid: &G, center: G::Location) -> impl Iterator<Item = G::Location> + '_ {
+    (0..grid.neighbor_count()).filter_map(move |direction| grid.neighbor(center, direction))
+}
+
+/// Counts the neighbors of the specified tile which are mines.
+pub fn count_neighboring_mines<G: Grid>(grid: &G, location: G::Location) -> u8 {
+    let mut count = 0_u8;
+    for neighbor in neighbors(grid, location) {
+        if grid.tile(neighbor).map_or(false, |tile| tile.state.is_mine()) {count += 1}
+    }
+    count
+}
+/// Returns the outcome of clicking the specified tile without affecting the field, or `None` if the location is out of bounds.
+pub fn peek<G: Grid>(grid: &G, location: G::Location) -> Option<ClickOutcome> {
+    let tile = grid.tile(location)?;
+    Some(tile.state.peek_local().unwrap_or_else(|| {
+        NonZeroU8::new(count_neighboring_mines(grid, location))
+            .map_or(ClickOutcome::OpenClearing, ClickOutcome::OpenNumber)
+    }))
+}
+/// Opens exactly one tile and returns the outcome of clicking it, or `None` if the location is out of bounds.
+pub fn open<G: Grid>(grid: &mut G, location: G::Location) -> Option<ClickOutcome> {
+    let outcome = peek(grid, location)?;
+    match outcome {
+        ClickOutcome::OpenClearing => grid.set_state(location, TileState::OpenEmpty),
+        ClickOutcome::OpenNumber(number) => grid.set_state(location, TileState::OpenNumber(number)),
+        _ => {}
+    }
+    Some(outcome)
+}
+/// Moves the mine from the specified tile to the first tile without a mine in row-major order which isn't void, retaining the flags on both, and returns the new location of the mine, or `None` if the tile doesn't have a mine, is out of bounds or there's no free tile to move the mine to.
+pub fn relocate_mine<G: Grid>(grid: &mut G, location: G::Location) -> Option<G::Location> {
+    if !grid.tile(location)?.state.is_mine() {return None}
+    let new_location = (0..grid.area())
+        .map(|index| grid.location_of(index))
+        .find(|&candidate| grid.tile(candidate).map_or(false, |tile| !tile.state.is_mine() && !tile.state.is_void()))?;
+    grid.update_state(new_location, |state| TileState::Mine(match state {
+        TileState::ClosedEmpty(flag) => flag,
+        // Open tiles can't hold a flag, and having them during the first click is an oddity anyway.
+        _ => Flag::NotFlagged,
+    }));
+    grid.update_state(location, |state| match state {
+        TileState::Mine(flag) => TileState::ClosedEmpty(flag),
+        state => state,
+    });
+    Some(new_location)
+}
+/// Checks that a chord can be performed on the specified tile, i.e. that it's an open number tile with as many flags around it as its number.
+pub fn check_chord<G: Grid>(grid: &G, location: G::Location) -> Result<(), Error<G::Location>> {
+    let tile = grid.tile(location).ok_or(Error::OutOfBounds(location))?;
+    let mines = if let TileState::OpenNumber(number) = tile.state {
+        number.get()
+    } else {
+        return Err(Error::NotANumber(location));
+    };
+    let mut flags = 0_u8;
+    for neighbor in neighbors(grid, location) {
+        if grid.tile(neighbor).map_or(false, |tile| tile.state.is_flagged()) {flags += 1}
+    }
+    if flags != mines {
+        return Err(Error::FlagCountMismatch {location, flags, mines});
+    }
+    Ok(())
+}
+/// Performs a chord on the specified tile, writing the outcome of opening the neighbor in every direction into the corresponding entry of `outcome`, or returns an error if the chord can't be performed.
+pub fn chord<G: Grid>(grid: &mut G, location: G::Location, outcome: &mut [ClickOutcome]) -> Result<(), Error<G::Location>> {
+    check_chord(grid, location)?;
+    for (direction, outcome) in outcome.iter_mut().enumerate() {
+        if let Some(neighbor) = grid.neighbor(location, direction) {
+            if grid.tile(neighbor).map_or(false, |tile| tile.state.is_closed() && !tile.state.is_flagged()) {
+                *outcome = open(grid, neighbor).unwrap_or_default();
+            }
+        }
+    }
+    Ok(())
+}
+/// Returns `true` if a clearing can be formed around the specified location, i.e. if it's inside the field, isn't a mine, isn't void and has no neighboring mines.
+pub fn is_clearing_anchor<G: Grid>(grid: &G, location: G::Location) -> bool {
+    grid.tile(location)
+        .map_or(false, |tile| !tile.state.is_mine() && !tile.state.is_void() && count_neighboring_mines(grid, location) == 0)
+}
+/// Returns the locations of all tiles in the clearing around the anchor location, in the order they were reached. Optionally includes the "shore" (tiles with numbers) and optionally leaves out the flagged tiles.
+///
+/// The clearing spreads from the anchor through the neighbors of every tile without neighboring mines, just like opening such a tile opens all of its neighbors, except for the void ones and, if requested, the flagged ones. Every tile is visited exactly once.
+pub fn clearing_tiles<G: Grid>(grid: &G, anchor_location: G::Location, include_shore: bool, keep_flagged: bool) -> Vec<G::Location> {
+    let mut visited = alloc::vec![false; grid.area()];
+    visited[grid.index_of(anchor_location)] = true;
+    // We're using a heap-based stack instead of recursion to avoid overflowing the thread stack on large clearings.
+    let mut stack = alloc::vec![anchor_location];
+    let mut tiles = Vec::new();
+    while let Some(location) = stack.pop() {
+        if count_neighboring_mines(grid, location) > 0 {
+            // A number tile is on the shore, and the clearing doesn't spread any further from it.
+            if include_shore {tiles.push(location)}
+            continue;
+        }
+        tiles.push(location);
+        // None of the neighbors can be a mine, since this tile would have a number otherwise. Holes in the field aren't a part of any clearing and don't let it spread through them, and neither do the flagged tiles if they are to be kept closed.
+        for neighbor in neighbors(grid, location) {
+            let visited = &mut visited[grid.index_of(neighbor)];
+            if !*visited && !grid.tile(neighbor).map_or(true, |tile| tile.state.is_void() || (keep_flagged && tile.state.is_flagged())) {
+                *visited = true;
+                stack.push(neighbor);
+            }
+        }
+    }
+    tiles
+}
+/// Fully opens the clearing around the specified tile, optionally including the "shore" (tiles with numbers) and optionally keeping the flagged tiles closed, and returns the amount of tiles which were opened and the total size of the clearing, or `None` if the location [can't anchor a clearing][is_clearing_anchor].
+pub fn open_clearing<G: Grid>(grid: &mut G, anchor_location: G::Location, include_shore: bool, keep_flagged: bool) -> Option<(usize, NonZeroUsize)> {
+    if !is_clearing_anchor(grid, anchor_location) {return None}
+    let tiles = clearing_tiles(grid, anchor_location, include_shore, keep_flagged);
+    let mut opened = 0_usize;
+    for &location in &tiles {
+        if grid.tile(location).map_or(false, |tile| matches!(tile.state, TileState::ClosedEmpty(_))) {
+            let number = NonZeroU8::new(count_neighboring_mines(grid, location));
+            grid.set_state(location, number.map_or(TileState::OpenEmpty, TileState::OpenNumber));
+            opened += 1;
+        }
+    }
+    // The anchor itself is always a part of the clearing.
+    NonZeroUsize::new(tiles.len()).map(|total| (opened, total))
+}
+/// Calculates the 3BV value of the field: one click per clearing, which also opens its shore, plus one click per remaining tile without a mine.
+pub fn calculate_3bv<G: Grid>(grid: &mut G) -> usize {
+    // Close all tiles, so that the clearings can be opened from scratch.
+    for index in 0..grid.area() {
+        let location = grid.location_of(index);
+        if grid.tile(location).map_or(false, |tile| tile.state.is_open()) {
+            grid.set_state(location, TileState::ClosedEmpty(Flag::NotFlagged));
+        }
+    }
+    // Every clearing takes one click, which also opens its shore.
+    let mut result = 0_usize;
+    for index in 0..grid.area() {
+        let location = grid.location_of(index);
+        if grid.tile(location).map_or(true, |tile| tile.state.is_open()) {continue}
+        if open_clearing(grid, location, true, false).is_some() {
+            result += 1;
+        }
+    }
+    // Every remaining tile without a mine takes a click of its own.
+    for index in 0..grid.area() {
+        let location = grid.location_of(index);
+        if grid.tile(location).map_or(false, |tile| tile.state.is_required_to_open()) {result += 1}
+    }
+    result
+}
+/// Checks that the numbers on all open tiles match the amount of mines around them, returning an error for the first one in row-major order which doesn't. `OpenEmpty` tiles count as showing zero.
+pub fn validate<G: Grid>(grid: &G) -> Result<(), InvalidFieldError<G::Location>> {
+    for index in 0..grid.area() {
+        let location = grid.location_of(index);
+        let shown = match grid.tile(location).map(|tile| &tile.state) {
+            Some(TileState::OpenEmpty) => 0,
+            Some(TileState::OpenNumber(number)) => number.get(),
+            _ => continue,
+        };
+        let neighboring_mines = count_neighboring_mines(grid, location);
+        if shown != neighboring_mines {
+            return Err(InvalidFieldError::InconsistentNumber {location, neighboring_mines});
+        }
+    }
+    Ok(())
+}
+
+/// Returns a row-major mask of the tiles which belong to the safe zone, or an error if one of the explicitly specified tiles is out of bounds.
+#[cfg(feature = "generation")]
+fn safe_zone_mask<G: Grid>(grid: &G, safe_zone: SafeZone<'_, G::Location>) -> Result<Vec<bool>, GenerationError<G::Location>> {
+    let mut mask = alloc::vec![false; grid.area()];
+    let mut exclude = |location: G::Location| {
+        if grid.tile(location).is_none() {
+            return Err(GenerationError::SafeSpotOutOfBounds(location));
+        }
+        mask[grid.index_of(location)] = true;
+        Ok(())
+    };
+    match safe_zone {
+        SafeZone::None => {},
+        SafeZone::Tile(location) => exclude(location)?,
+        SafeZone::Neighborhood(location) => {
+            exclude(location)?; // Check the center, since the neighbors outside the field are skipped.
+            for neighbor in neighbors(grid, location) {
+                exclude(neighbor)?;
+            }
+        },
+        SafeZone::Custom(tiles) => {
+            for &location in tiles {
+                exclude(location)?;
+            }
+        },
+    }
+    Ok(mask)
+}
+/// Adds exactly the specified amount of mines on tiles which don't already contain one, aren't void and are outside the safe zone, using a partial Fisher–Yates shuffle over the candidate tiles. The field is not modified if that's impossible.
+#[cfg(feature = "generation")]
+#[allow(clippy::cast_possible_truncation)]
+pub fn populate_exact<G, R>(grid: &mut G, num_mines: usize, safe_zone: SafeZone<'_, G::Location>, rng: &mut R) -> Result<(), GenerationError<G::Location>>
+where G: Grid, R: Rng + ?Sized {
+    let excluded = safe_zone_mask(grid, safe_zone)?;
+    // Collect the row-major indices of all tiles which can receive a mine.
+    let mut candidates = (0..grid.area())
+        .filter(|&index| !excluded[index] && grid.tile(grid.location_of(index)).map_or(false, |tile| !tile.state.is_mine() && !tile.state.is_void()))
+        .collect::<Vec<_>>();
+    if candidates.len() < num_mines {
+        return Err(GenerationError::TooManyMines {requested: num_mines, available: candidates.len()});
+    }
+    // Partial Fisher–Yates: after the i-th iteration, the first i candidates are a uniformly chosen sample of the whole list.
+    for i in 0..num_mines {
+        // Sampling a usize would make the result depend on the pointer width, so we're sampling a u64 instead.
+        let j = i + rng.gen_range(0, (candidates.len() - i) as u64) as usize;
+        candidates.swap(i, j);
+        let location = grid.location_of(candidates[i]);
+        grid.set_state(location, TileState::Mine(Flag::NotFlagged)); // Install the mine.
+    }
+    Ok(())
+}
@@ -0,0 +1,524 @@
+//! Fields made of hexagonal tiles.
+//!
+//! A [`HexField`][hf] stores the same [tiles][tile] as a regular field, but every tile touches 6 others instead of 8. The tiles are pointy-topped and addressed with **odd-r offset coordinates**: the first coordinate is the column and the second one is the row, just like on a regular field, and every odd row is shifted right by half a tile. Because of that, which tiles are the neighbors of a tile depends on the parity of its row:
+//!
+//! ```text
+//!  / \ / \ / \
+//! |0,0|1,0|2,0|
+//!  \ / \ / \ / \
+//!   |0,1|1,1|2,1|
+//!  / \ / \ / \ /
+//! |0,2|1,2|2,2|
+//!  \ / \ / \ /
+//! ```
+//!
+//! Here, `[1, 1]` touches `[1, 0]`, `[2, 0]`, `[2, 1]`, `[2, 2]`, `[1, 2]` and `[0, 1]`, while `[1, 2]` touches `[0, 1]`, `[1, 1]`, `[2, 2]`, `[1, 3]`, `[0, 3]` and `[0, 2]`. Hexagonal fields are always bounded.
+//!
+//! # Usage
+//! ```
+//! # use sweeper::{HexField, TileState, Flag, ClickOutcome};
+//! # use core::num::NonZeroUsize;
+//! #
+//! let mut field = HexField::<(), ()>::empty([NonZeroUsize::new(5).unwrap(); 2]);
+//! field[[2, 1]].state = TileState::Mine(Flag::NotFlagged);
+//! // On an odd row, the tiles above and below are shifted to the right.
+//! assert_eq!(field.count_neighboring_mines([2, 0]), 1);
+//! assert_eq!(field.count_neighboring_mines([3, 0]), 1);
+//! assert_eq!(field.count_neighboring_mines([1, 0]), 0);
+//!
+//! // Opening the far corner opens the whole clearing around the mine.
+//! let (opened, _) = field.open_clearing([4, 4], true).unwrap();
+//! assert_eq!(opened, 24);
+//! assert!(field.solved());
+//! ```
+//!
+//! [hf]: struct.HexField.html "HexField — a playfield made of hexagonal tiles"
+//! [tile]: ../struct.Tile.html "Tile — a tile on a Minesweeper field"
+
+use core::{
+    ops::{Index, IndexMut},
+    num::NonZeroUsize,
+    iter::FusedIterator,
+};
+#[cfg(feature = "serialization")]
+use alloc::vec::Vec;
+#[cfg(feature = "serialization")]
+use serde::{
+    Serialize, Deserialize,
+    ser::{Serializer, SerializeStruct},
+    de::Deserializer,
+};
+#[cfg(feature = "generation")]
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "generation")]
+use rand_pcg::Pcg64;
+#[cfg(feature = "generation")]
+use crate::{SafeZone, GenerationError};
+use crate::{
+    Field, FieldDimensions, FieldCoordinates,
+    Tile, TileState, ClickOutcome, Error, InvalidFieldError,
+    EnumerateTiles, EnumerateTilesMut,
+    Topology,
+    grid::{self, Grid},
+};
+
+/// The offsets of the neighbors of a tile on an even row, in the order of [`HexChordOutcome`][hco].
+///
+/// [hco]: type.HexChordOutcome.html "HexChordOutcome — the outcome of a chord operation on a hexagonal field"
+const EVEN_ROW_OFFSETS: [[isize; 2]; 6] = [[-1, -1], [0, -1], [1, 0], [0, 1], [-1, 1], [-1, 0]];
+/// The offsets of the neighbors of a tile on an odd row, which is shifted right by half a tile.
+const ODD_ROW_OFFSETS: [[isize; 2]; 6] = [[0, -1], [1, -1], [1, 0], [1, 1], [0, 1], [-1, 0]];
+/// The current version of the layout used to serialize hexagonal fields.
+#[cfg(feature = "serialization")]
+const VERSION: u32 = 1;
+
+/// The outcome of a [chord operation][m_chord] on a hexagonal field.
+///
+/// The entries are the neighboring tiles in clockwise order, starting from top-left: ↖, ↗, →, ↘, ↙, ←.
+///
+/// [m_chord]: struct.HexField.html#method.chord "chord — performs a chord operation on the specified tile"
+pub type HexChordOutcome = [ClickOutcome; 6];
+
+/// Returns the location of the neighbor of the specified tile in the specified direction of [`HexChordOutcome`][hco], or `None` if it's outside the field.
+///
+/// [hco]: type.HexChordOutcome.html "HexChordOutcome — the outcome of a chord operation on a hexagonal field"
+fn hex_neighbor(dimensions: FieldDimensions, center: FieldCoordinates, direction: usize) -> Option<FieldCoordinates> {
+    let offsets = if center[1] % 2 == 0 {&EVEN_ROW_OFFSETS} else {&ODD_ROW_OFFSETS};
+    Topology::Bounded.offset(dimensions, center, offsets[direction])
+}
+
+/// Iterates over the locations of the neighbors of a tile on a hexagonal field.
+///
+/// The neighbors are produced in the order of [`HexChordOutcome`][hco], skipping the ones outside the field. Just like [`NeighborLocations`][nl], the iterator doesn't borrow the field.
+///
+/// # Usage
+/// ```
+/// # use sweeper::HexNeighborLocations;
+/// # use core::num::NonZeroUsize;
+/// #
+/// // Check every tile of every field up to 5x5 against the definition in cube coordinates.
+/// let to_cube = |x: isize, y: isize| {
+///     let q = x - (y - (y & 1)) / 2;
+///     [q, y, -q - y]
+/// };
+/// for width in 1..=5 {
+///     for height in 1..=5 {
+///         let dimensions = [NonZeroUsize::new(width).unwrap(), NonZeroUsize::new(height).unwrap()];
+///         for y in 0..height as isize {
+///             for x in 0..width as isize {
+///                 let center = to_cube(x, y);
+///                 let mut expected = Vec::new();
+///                 for ny in 0..height as isize {
+///                     for nx in 0..width as isize {
+///                         let other = to_cube(nx, ny);
+///                         let distance = (0..3).map(|i| (center[i] - other[i]).abs()).max().unwrap();
+///                         if distance == 1 {expected.push([nx as usize, ny as usize])}
+///                     }
+///                 }
+///                 let mut actual: Vec<_> = HexNeighborLocations::new(dimensions, [x as usize, y as usize]).collect();
+///                 actual.sort_by_key(|&[x, y]| (y, x));
+///                 assert_eq!(actual, expected);
+///             }
+///         }
+///         assert_eq!(HexNeighborLocations::new(dimensions, [width, 0]).count(), 0);
+///     }
+/// }
+/// ```
+///
+/// [hco]: type.HexChordOutcome.html "HexChordOutcome — the outcome of a chord operation on a hexagonal field"
+/// [nl]: ../iter/struct.NeighborLocations.html "NeighborLocations — iterates over the locations of the neighbors of a tile"
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HexNeighborLocations {
+    dimensions: FieldDimensions,
+    center: FieldCoordinates,
+    direction: usize,
+}
+impl HexNeighborLocations {
+    /// Creates an iterator over the neighbors of the specified tile on a hexagonal field with the specified dimensions. If the tile itself is outside the field, the iterator is empty.
+    #[inline]
+    pub const fn new(dimensions: FieldDimensions, center: FieldCoordinates) -> Self {
+        let inside = center[0] < dimensions[0].get() && center[1] < dimensions[1].get();
+        Self {dimensions, center, direction: if inside {0} else {EVEN_ROW_OFFSETS.len()}}
+    }
+    /// Returns the location of the tile whose neighbors are being iterated over.
+    #[inline(always)]
+    pub const fn center(&self) -> FieldCoordinates {
+        self.center
+    }
+}
+impl Iterator for HexNeighborLocations {
+    type Item = FieldCoordinates;
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.direction < EVEN_ROW_OFFSETS.len() {
+            let direction = self.direction;
+            self.direction += 1;
+            if let Some(location) = hex_neighbor(self.dimensions, self.center, direction) {
+                return Some(location);
+            }
+        }
+        None
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(EVEN_ROW_OFFSETS.len().saturating_sub(self.direction)))
+    }
+}
+impl FusedIterator for HexNeighborLocations {}
+
+/// A playfield made of hexagonal tiles.
+///
+/// This works just like a regular [`Field`][field], except that every tile has up to 6 neighbors, arranged as described in the [module-level documentation][hex]. The tiles themselves are the same, so everything which only looks at one tile at a time, like flagging, works the same way. Clearings are opened right away by [`open_clearing`][m_open_clearing] instead of through a separate clearing reference.
+///
+/// [field]: ../struct.Field.html "Field — represents a playfield"
+/// [hex]: index.html "hex — fields made of hexagonal tiles"
+/// [m_open_clearing]: #method.open_clearing "open_clearing — fully opens the clearing around the specified tile"
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HexField<Ct, Cf> {
+    field: Field<Ct, Cf>,
+}
+impl<Ct, Cf> HexField<Ct, Cf>
+where Ct: Default {
+    /// Creates an empty hexagonal field filled with unopened tiles, with the given dimensions.
+    #[inline]
+    #[must_use = "this performs a memory allocation as big as the area of the field"]
+    pub fn empty(dimensions: FieldDimensions) -> Self {
+        Self {field: Field::empty(dimensions)}
+    }
+}
+impl<Ct, Cf> HexField<Ct, Cf> {
+    /// Reinterprets the tiles of a regular field as hexagonal ones. The topology of the field is discarded.
+    ///
+    /// The numbers on the open tiles are kept as is, which means that they're most likely wrong for the hexagonal layout. Use [`validate`][m_validate] to check them.
+    ///
+    /// [m_validate]: #method.validate "validate — checks that the numbers on all open tiles match the amount of mines around them"
+    #[inline]
+    pub const fn from_field(field: Field<Ct, Cf>) -> Self {
+        Self {field: field.with_topology(Topology::Bounded)}
+    }
+    /// Returns the underlying regular field, which can be used to iterate over rows and columns or to store the tiles in the formats which only support regular fields.
+    ///
+    /// The numbers on the open tiles stay the ones for the hexagonal layout, so the methods of the regular field which look at the neighbors of a tile will give wrong results.
+    #[inline(always)]
+    #[allow(clippy::missing_const_for_fn)] // Destructors can't be run in a const fn.
+    pub fn into_field(self) -> Field<Ct, Cf> {
+        self.field
+    }
+    /// Returns a reference to the underlying regular field. See [`into_field`][m_into_field] for the caveats.
+    ///
+    /// [m_into_field]: #method.into_field "into_field — returns the underlying regular field"
+    #[inline(always)]
+    pub const fn as_field(&self) -> &Field<Ct, Cf> {
+        &self.field
+    }
+    /// Checks that the numbers on all open tiles match the amount of mines among their 6 neighbors. See [`Field::validate`][m_validate] for more.
+    ///
+    /// # Errors
+    /// Returns [`InconsistentNumber`][e_in] for the first tile in row-major order which shows a wrong number.
+    ///
+    /// [m_validate]: ../struct.Field.html#method.validate "validate — checks that the numbers on all open tiles match the amount of mines around them"
+    /// [e_in]: ../enum.InvalidFieldError.html#variant.InconsistentNumber "InvalidFieldError::InconsistentNumber — an open tile shows a different number than the amount of mines around it"
+    pub fn validate(&self) -> Result<(), InvalidFieldError> {
+        grid::validate(self)
+    }
+    /// Returns the width and height of the field.
+    #[inline(always)]
+    pub const fn dimensions(&self) -> FieldDimensions {
+        self.field.dimensions()
+    }
+    /// Returns `true` if the field is fully solved (doesn't have any more safe tiles to open), `false` otherwise.
+    #[inline]
+    pub fn solved(&self) -> bool {
+        self.field.solved()
+    }
+    /// Returns the number of tiles which are not mines and are yet to be opened, which is also the number of tiles which are left to open to solve the field.
+    #[inline]
+    pub fn tiles_to_open(&self) -> usize {
+        self.field.tiles_to_open()
+    }
+    /// Returns `true` if the specified location is inside the field, `false` otherwise.
+    #[inline(always)]
+    pub const fn contains(&self, coordinates: FieldCoordinates) -> bool {
+        self.field.contains(coordinates)
+    }
+    /// Returns the tile at the specified location, or `None` if the location is out of bounds.
+    #[inline]
+    pub fn get(&self, coordinates: FieldCoordinates) -> Option<&Tile<Ct, Cf>> {
+        self.field.get(coordinates)
+    }
+    /// Returns a mutable reference to the tile at the specified location, or `None` if the location is out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, coordinates: FieldCoordinates) -> Option<&mut Tile<Ct, Cf>> {
+        self.field.get_mut(coordinates)
+    }
+    /// Returns an iterator over all tiles of the field in row-major order.
+    #[inline(always)]
+    pub fn all_tiles(&self) -> core::slice::Iter<'_, Tile<Ct, Cf>> {
+        self.field.all_tiles()
+    }
+    /// Returns an iterator over mutable references to all tiles of the field in row-major order.
+    #[inline(always)]
+    pub fn all_tiles_mut(&mut self) -> core::slice::IterMut<'_, Tile<Ct, Cf>> {
+        self.field.all_tiles_mut()
+    }
+    /// Returns an iterator over all tiles of the field along with their locations, in row-major order.
+    #[inline(always)]
+    pub fn enumerate_tiles(&self) -> EnumerateTiles<'_, Ct, Cf> {
+        self.field.enumerate_tiles()
+    }
+    /// Returns an iterator over mutable references to all tiles of the field along with their locations, in row-major order.
+    #[inline(always)]
+    pub fn enumerate_tiles_mut(&mut self) -> EnumerateTilesMut<'_, Ct, Cf> {
+        self.field.enumerate_tiles_mut()
+    }
+    /// Returns an iterator over the locations of the neighbors of the specified tile which are inside the field, in the order of [`HexChordOutcome`][hco].
+    ///
+    /// [hco]: type.HexChordOutcome.html "HexChordOutcome — the outcome of a chord operation on a hexagonal field"
+    #[inline(always)]
+    pub const fn neighbor_locations(&self, location: FieldCoordinates) -> HexNeighborLocations {
+        HexNeighborLocations::new(self.dimensions(), location)
+    }
+    /// Counts the neighbors of the specified tile which are mines. A location outside the field has no neighbors.
+    #[must_use = "this is a lookup of up to 6 tiles"]
+    pub fn count_neighboring_mines(&self, location: FieldCoordinates) -> u8 {
+        grid::count_neighboring_mines(self, location)
+    }
+    /// Returns the outcome of clicking the specified tile **without affecting the field**, or `None` if the index is out of bounds.
+    pub fn peek(&self, coordinates: FieldCoordinates) -> Option<ClickOutcome> {
+        grid::peek(self, coordinates)
+    }
+    /// Opens **exactly one** tile and returns the outcome of clicking it, or `None` if the index is out of bounds. **Chords and clearings are not handled** and must be executed manually, usually with [`chord`][m_chord] and [`open_clearing`][m_open_clearing].
+    ///
+    /// [m_chord]: #method.chord "chord — performs a chord operation on the specified tile"
+    /// [m_open_clearing]: #method.open_clearing "open_clearing — fully opens the clearing around the specified tile"
+    pub fn open(&mut self, coordinates: FieldCoordinates) -> Option<ClickOutcome> {
+        grid::open(self, coordinates)
+    }
+    /// Performs a chord on the specified tile, opening all of its closed unflagged neighbors if the number on it matches the amount of flags around it.
+    ///
+    /// Just like with [`Field::chord`][m_chord], the returned array contains the outcome of opening every neighbor, and the clearings reported by them must be opened manually. Nothing happens if the chord can't be performed; use [`try_chord`][m_try_chord] to find out why.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{HexField, TileState, Flag, ClickOutcome};
+    /// # use core::num::{NonZeroUsize, NonZeroU8};
+    /// #
+    /// let mut field = HexField::<(), ()>::empty([NonZeroUsize::new(3).unwrap(); 2]);
+    /// field[[2, 0]].state = TileState::Mine(Flag::Flagged);
+    /// field.open([1, 1]);
+    /// assert_eq!(field[[1, 1]].state, TileState::OpenNumber(NonZeroU8::new(1).unwrap()));
+    ///
+    /// let outcome = field.chord([1, 1]);
+    /// // The flagged mine is the top-right neighbor, so it stays closed.
+    /// assert_eq!(outcome[1], ClickOutcome::Nothing);
+    /// assert_eq!(outcome[5], ClickOutcome::OpenClearing);
+    /// assert_eq!(outcome[2], ClickOutcome::OpenNumber(NonZeroU8::new(1).unwrap()));
+    /// // Only the left corners are still closed.
+    /// assert_eq!(field.tiles_to_open(), 2);
+    /// ```
+    ///
+    /// [m_chord]: ../struct.Field.html#method.chord "chord — performs a chord operation on the specified tile"
+    /// [m_try_chord]: #method.try_chord "try_chord — performs a chord on the specified tile, or returns an error if the chord can't be performed"
+    pub fn chord(&mut self, coordinates: FieldCoordinates) -> HexChordOutcome {
+        self.try_chord(coordinates).unwrap_or_default()
+    }
+    /// Performs a chord on the specified tile, or returns an error if the chord can't be performed.
+    ///
+    /// # Errors
+    /// Returns [`OutOfBounds`][e_oob] if the location is outside the field, [`NotANumber`][e_nan] if the tile isn't an open number tile and [`FlagCountMismatch`][e_fcm] if the amount of flags around the tile doesn't match its number.
+    ///
+    /// [e_oob]: ../enum.Error.html#variant.OutOfBounds "Error::OutOfBounds — the specified location is outside the field"
+    /// [e_nan]: ../enum.Error.html#variant.NotANumber "Error::NotANumber — a chord was performed on a tile which isn't an open number tile"
+    /// [e_fcm]: ../enum.Error.html#variant.FlagCountMismatch "Error::FlagCountMismatch — a chord was performed on a number tile which is surrounded by a different amount of flags than its number"
+    pub fn try_chord(&mut self, coordinates: FieldCoordinates) -> Result<HexChordOutcome, Error> {
+        let mut result = [ClickOutcome::Nothing; 6];
+        grid::chord(self, coordinates, &mut result)?;
+        Ok(result)
+    }
+    /// Fully opens the clearing around the specified tile, optionally including the "shore" (tiles with numbers), or returns `None` if the location is out of bounds, contains a mine, is void or has 1 or more neighboring mines.
+    ///
+    /// The returned values are the same as the ones of [`ClearingMut::open`][m_open]: the amount of tiles which were opened and the total size of the clearing.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{HexField, TileState, Flag};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let mut field = HexField::<(), ()>::empty([NonZeroUsize::new(3).unwrap(); 2]);
+    /// field[[2, 2]].state = TileState::Mine(Flag::NotFlagged);
+    /// // Numbers can't anchor a clearing.
+    /// assert_eq!(field.open_clearing([1, 1], true), None);
+    /// // The mine only has three neighbors, so the other five tiles form the clearing.
+    /// assert_eq!(field.clone().open_clearing([0, 0], false), Some((5, NonZeroUsize::new(5).unwrap())));
+    /// assert_eq!(field.open_clearing([0, 0], true), Some((8, NonZeroUsize::new(8).unwrap())));
+    /// assert!(field.solved());
+    /// ```
+    ///
+    /// [m_open]: ../struct.ClearingMut.html#method.open "open — fully opens the clearing on the field"
+    pub fn open_clearing(&mut self, anchor_location: FieldCoordinates, include_shore: bool) -> Option<(usize, NonZeroUsize)> {
+        grid::open_clearing(self, anchor_location, include_shore, false)
+    }
+    /// Calculates the 3BV value of the field, using the same rules as [`Field::calculate_3bv`][m_3bv] with hexagonal clearings.
+    ///
+    /// Since the field is modified in an undefined way in the process, it is taken by value.
+    ///
+    /// [m_3bv]: ../struct.Field.html#method.calculate_3bv "calculate_3bv — calculates the 3BV value of the field"
+    #[must_use = "calculating the 3BV value for any possible field requires traversing the entire field two times and opening clearings"]
+    pub fn calculate_3bv(mut self) -> usize {
+        grid::calculate_3bv(&mut self)
+    }
+}
+#[cfg(feature = "generation")]
+impl<Ct, Cf> HexField<Ct, Cf> {
+    /// Adds exactly the specified amount of mines while keeping the specified [safe zone][sz] free of mines, using the thread-local random number generator. A `Neighborhood` safe zone covers the 6 neighbors of the tile instead of 8.
+    ///
+    /// # Errors
+    /// See [`Field::populate_exact_with_rng`][m_pewr].
+    ///
+    /// [sz]: ../enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+    /// [m_pewr]: ../struct.Field.html#method.populate_exact_with_rng "populate_exact_with_rng — adds exactly the specified amount of mines using the specified random number generator"
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn populate_exact<'a>(&mut self, num_mines: usize, safe_zone: impl Into<SafeZone<'a>>) -> Result<(), GenerationError> {
+        self.populate_exact_with_rng(num_mines, safe_zone, &mut rand::thread_rng())
+    }
+    /// Adds exactly the specified amount of mines while keeping the specified [safe zone][sz] free of mines, using the specified random number generator. A `Neighborhood` safe zone covers the 6 neighbors of the tile instead of 8.
+    ///
+    /// # Errors
+    /// See [`Field::populate_exact_with_rng`][m_pewr].
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{HexField, GenerationError, SafeZone, ClickOutcome};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let mut field = HexField::<(), ()>::empty([NonZeroUsize::new(8).unwrap(); 2]);
+    /// field.populate_exact_from_seed(20, SafeZone::Neighborhood([3, 3]), 1337)?;
+    /// assert_eq!(field.all_tiles().filter(|t| t.state.is_mine()).count(), 20);
+    /// assert_eq!(field.open([3, 3]), Some(ClickOutcome::OpenClearing));
+    /// # Ok::<(), GenerationError>(())
+    /// ```
+    ///
+    /// [sz]: ../enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+    /// [m_pewr]: ../struct.Field.html#method.populate_exact_with_rng "populate_exact_with_rng — adds exactly the specified amount of mines using the specified random number generator"
+    pub fn populate_exact_with_rng<'a, R>(&mut self, num_mines: usize, safe_zone: impl Into<SafeZone<'a>>, rng: &mut R) -> Result<(), GenerationError>
+    where R: Rng + ?Sized {
+        grid::populate_exact(self, num_mines, safe_zone.into(), rng)
+    }
+    /// Adds exactly the specified amount of mines while keeping the specified [safe zone][sz] free of mines. The mine layout is fully determined by the seed, with the same guarantees as the ones of [`Field::populate_exact_from_seed`][m_pefs].
+    ///
+    /// # Errors
+    /// See [`Field::populate_exact_with_rng`][m_pewr].
+    ///
+    /// [sz]: ../enum.SafeZone.html "SafeZone — the set of tiles which are kept free of mines during generation"
+    /// [m_pefs]: ../struct.Field.html#method.populate_exact_from_seed "populate_exact_from_seed — adds exactly the specified amount of mines using a random number generator with the specified seed"
+    /// [m_pewr]: ../struct.Field.html#method.populate_exact_with_rng "populate_exact_with_rng — adds exactly the specified amount of mines using the specified random number generator"
+    #[inline]
+    pub fn populate_exact_from_seed<'a>(&mut self, num_mines: usize, safe_zone: impl Into<SafeZone<'a>>, seed: u64) -> Result<(), GenerationError> {
+        self.populate_exact_with_rng(num_mines, safe_zone, &mut Pcg64::seed_from_u64(seed))
+    }
+}
+impl<Ct, Cf> Grid for HexField<Ct, Cf> {
+    type Location = FieldCoordinates;
+    type Payload = Ct;
+    type Flag = Cf;
+    #[inline(always)]
+    fn area(&self) -> usize {
+        self.field.area()
+    }
+    #[inline(always)]
+    fn index_of(&self, location: FieldCoordinates) -> usize {
+        self.field.index_of(location)
+    }
+    #[inline(always)]
+    fn location_of(&self, index: usize) -> FieldCoordinates {
+        self.field.location_of(index)
+    }
+    #[inline(always)]
+    fn neighbor_count(&self) -> usize {
+        EVEN_ROW_OFFSETS.len()
+    }
+    #[inline(always)]
+    fn neighbor(&self, center: FieldCoordinates, direction: usize) -> Option<FieldCoordinates> {
+        hex_neighbor(self.dimensions(), center, direction)
+    }
+    #[inline(always)]
+    fn tile(&self, location: FieldCoordinates) -> Option<&Tile<Ct, Cf>> {
+        self.get(location)
+    }
+    #[inline(always)]
+    fn update_state<F>(&mut self, location: FieldCoordinates, update: F)
+    where F: FnOnce(TileState<Cf>) -> TileState<Cf> {
+        self.field.update_state(location, update);
+    }
+}
+impl<Ct, Cf> Index<FieldCoordinates> for HexField<Ct, Cf> {
+    type Output = Tile<Ct, Cf>;
+    /// Returns the tile at the column `index[0]` and row `index[1]`, both starting at zero.
+    ///
+    /// # Panics
+    /// Index checking is enabled for this method. For a version which returns an `Option` instead of panicking if the index is out of bounds, see `get`.
+    #[inline(always)]
+    fn index(&self, coordinates: FieldCoordinates) -> &Self::Output {
+        &self.field[coordinates]
+    }
+}
+impl<Ct, Cf> IndexMut<FieldCoordinates> for HexField<Ct, Cf> {
+    /// Returns the tile at the column `index[0]` and row `index[1]`, both starting at zero.
+    ///
+    /// # Panics
+    /// Index checking is enabled for this method. For a version which returns an `Option` instead of panicking if the index is out of bounds, see `get_mut`.
+    #[inline(always)]
+    fn index_mut(&mut self, coordinates: FieldCoordinates) -> &mut Self::Output {
+        &mut self.field[coordinates]
+    }
+}
+
+/// The layout of a serialized hexagonal field, which is read before being checked.
+#[cfg(feature = "serialization")]
+#[derive(Deserialize)]
+#[serde(rename = "HexField")]
+struct RawHexField<Ct, Cf> {
+    version: u32,
+    dimensions: FieldDimensions,
+    storage: Vec<Tile<Ct, Cf>>,
+}
+#[cfg(feature = "serialization")]
+impl<Ct, Cf> Serialize for HexField<Ct, Cf>
+where Ct: Serialize,
+      Cf: Serialize {
+    /// Serializes the field together with the version of the layout, which is separate from the one of regular fields.
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let storage = self.all_tiles().as_slice();
+        let mut s = s.serialize_struct("HexField", 3)?;
+        s.serialize_field("version", &VERSION)?;
+        s.serialize_field("dimensions", &self.dimensions())?;
+        s.serialize_field("storage", storage)?;
+        s.end()
+    }
+}
+#[cfg(feature = "serialization")]
+impl<'de, Ct, Cf> Deserialize<'de> for HexField<Ct, Cf>
+where Ct: Deserialize<'de>,
+      Cf: Deserialize<'de> {
+    /// Deserializes a hexagonal field and [validates][m_validate] it.
+    ///
+    /// [m_validate]: #method.validate "validate — checks that the numbers on all open tiles match the amount of mines around them"
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        use serde::de::{Error as _, Unexpected};
+        let RawHexField {version, dimensions, storage} = RawHexField::deserialize(d)?;
+        if version != VERSION {
+            return Err(D::Error::invalid_value(
+                Unexpected::Unsigned(version.into()),
+                &"a hexagonal field serialization version supported by this release",
+            ));
+        }
+        let area = dimensions[0].get().checked_mul(dimensions[1].get())
+            .ok_or_else(|| D::Error::custom(InvalidFieldError::<FieldCoordinates>::AreaOverflow))?;
+        let found = storage.len();
+        let field = Field::from_dimensions_and_storage(dimensions, storage)
+            .ok_or_else(|| D::Error::custom(InvalidFieldError::<FieldCoordinates>::StorageLengthMismatch {expected: area, found}))?;
+        let field = Self {field};
+        field.validate().map_err(D::Error::custom)?;
+        Ok(field)
+    }
+}
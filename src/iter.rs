@@ -5,19 +5,39 @@
 //! - [`RowIter`][rowiter] — iterates over a single field row
 //! - [`FieldRowsIter`][fri] — iterates over the rows of a field (each item is a [`RowIter`][rowiter])
 //! - [`FieldColumnsIter`][fci] — iterates over the columns of a field (each item is a [`ColumnIter`][columniter])
+//! - [`RowIterMut`][rowitermut], [`ColumnIterMut`][columnitermut], [`FieldRowsIterMut`][frim] and [`FieldColumnsIterMut`][fcim] — mutable versions of the above
+//! - [`NeighborLocations`][nl] — iterates over the locations of the neighbors of a tile
+//! - [`Neighbors`][neighbors] — iterates over the neighbors of a tile along with their locations
+//! - [`NeighborsMut`][neighbors_mut] — iterates over mutable references to the neighbors of a tile along with their locations
+//! - [`EnumerateTiles`][et] and [`EnumerateTilesMut`][etm] — iterate over all tiles of a field along with their locations
+//! - [`FilteredTiles`][ft] — iterates over the tiles of a field which are in a certain state, along with their locations
 //!
 //! [rowiter]: struct.RowIter.html "RowIter — iterates over a single field row"
 //! [columniter]: struct.ColumnIter.html "ColumnIter — iterates over a single field column"
 //! [fri]: struct.FieldRowsIter.html "FieldRowsIter — an iterator over the rows of a field"
 //! [fci]: struct.FieldColumnsIter.html "an iterator over the columns of a field"
+//! [rowitermut]: struct.RowIterMut.html "RowIterMut — iterates over mutable references to the tiles of a single field row"
+//! [columnitermut]: struct.ColumnIterMut.html "ColumnIterMut — iterates over mutable references to the tiles of a single field column"
+//! [frim]: struct.FieldRowsIterMut.html "FieldRowsIterMut — an iterator over the mutable rows of a field"
+//! [fcim]: struct.FieldColumnsIterMut.html "FieldColumnsIterMut — an iterator over the mutable columns of a field"
+//! [nl]: struct.NeighborLocations.html "NeighborLocations — iterates over the locations of the neighbors of a tile"
+//! [neighbors]: struct.Neighbors.html "Neighbors — iterates over the neighbors of a tile along with their locations"
+//! [neighbors_mut]: struct.NeighborsMut.html "NeighborsMut — iterates over mutable references to the neighbors of a tile along with their locations"
+//! [et]: struct.EnumerateTiles.html "EnumerateTiles — iterates over all tiles of a field along with their locations"
+//! [etm]: struct.EnumerateTilesMut.html "EnumerateTilesMut — iterates over mutable references to all tiles of a field along with their locations"
+//! [ft]: struct.FilteredTiles.html "FilteredTiles — iterates over the tiles of a field which are in a certain state, along with their locations"
 
 use core::{
     ops::{Range, Index},
-    iter::FusedIterator
+    iter::{FusedIterator, Enumerate},
+    marker::PhantomData,
+    slice::{Iter, IterMut, ChunksMut},
+    fmt,
 };
 use super::{
-    Tile,
-    Field
+    Tile, TileState,
+    Field, FieldDimensions, FieldCoordinates,
+    Topology, Neighborhood,
 };
 
 /// Iterates over a single field row.
@@ -153,11 +173,11 @@ impl<'f, Ct, Cf> ColumnIter<'f, Ct, Cf> {
     /// Creates an iterator over the specified column of the specified field.
     ///
     /// # Panics
-    /// Panics if the specified row is out of range.
+    /// Panics if the specified column is out of range.
     #[inline(always)]
     pub fn new(field: &'f Field<Ct, Cf>, column: usize) -> Self {
         assert!(column < field.dimensions()[0].get());
-        Self {field, column, index: 0..field.dimensions()[0].get()}
+        Self {field, column, index: 0..field.dimensions()[1].get()}
     }
     /// Returns the tile at the specified row, or `None` if such a row doesn't exist. The column for which the iterator was created is used.
     #[inline(always)]
@@ -354,4 +374,759 @@ impl<'f, Ct, Cf> ExactSizeIterator for FieldColumnsIter<'f, Ct, Cf> {
         self.index.end - self.index.start
     }
 }
-impl<Ct, Cf> FusedIterator for FieldColumnsIter<'_, Ct, Cf> {}
\ No newline at end of file
+impl<Ct, Cf> FusedIterator for FieldColumnsIter<'_, Ct, Cf> {}
+
+/// Iterates over mutable references to the tiles of a single field row.
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Field, TileState, Flag};
+/// # use core::num::NonZeroUsize;
+/// #
+/// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(9).unwrap(), NonZeroUsize::new(4).unwrap()]);
+/// // Flag the whole last row.
+/// for tile in field.row_mut(3) {
+///     tile.state = TileState::ClosedEmpty(Flag::Flagged);
+/// }
+/// assert!(field.row(3).all(|tile| tile.state.is_flagged()));
+/// assert!(!field.row(2).any(|tile| tile.state.is_flagged()));
+/// ```
+#[derive(Debug)]
+pub struct RowIterMut<'f, Ct, Cf> {
+    tiles: IterMut<'f, Tile<Ct, Cf>>,
+}
+impl<'f, Ct, Cf> RowIterMut<'f, Ct, Cf> {
+    /// Creates an iterator over the specified row of the specified field.
+    ///
+    /// # Panics
+    /// Panics if the specified row is out of range.
+    #[inline]
+    pub fn new(field: &'f mut Field<Ct, Cf>, row: usize) -> Self {
+        assert!(row < field.dimensions()[1].get());
+        let width = field.dimensions()[0].get();
+        Self {tiles: field.all_tiles_mut().into_slice()[row * width..(row + 1) * width].iter_mut()}
+    }
+}
+impl<'f, Ct, Cf> Iterator for RowIterMut<'f, Ct, Cf> {
+    type Item = &'f mut Tile<Ct, Cf>;
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.tiles.next()
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+impl<Ct, Cf> DoubleEndedIterator for RowIterMut<'_, Ct, Cf> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.tiles.next_back()
+    }
+}
+impl<Ct, Cf> ExactSizeIterator for RowIterMut<'_, Ct, Cf> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.tiles.len()
+    }
+}
+impl<Ct, Cf> FusedIterator for RowIterMut<'_, Ct, Cf> {}
+
+/// Iterates over mutable references to the tiles of a single field column.
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Field, TileState, Flag};
+/// # use core::num::NonZeroUsize;
+/// #
+/// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(9).unwrap(), NonZeroUsize::new(8).unwrap()]);
+/// // Place a mine at the bottom of the last column.
+/// field.column_mut(8).next_back().unwrap().state = TileState::Mine(Flag::NotFlagged);
+/// assert!(field[[8, 7]].state.is_mine());
+/// assert_eq!(field.column_mut(8).len(), 8);
+/// ```
+#[derive(Debug)]
+pub struct ColumnIterMut<'f, Ct, Cf> {
+    // Columns aren't contiguous in the row-major storage, and several columns can be borrowed at once by a FieldColumnsIterMut, so they can't be represented by slices.
+    tiles: *mut Tile<Ct, Cf>,
+    width: usize,
+    column: usize,
+    index: Range<usize>,
+    _field: PhantomData<&'f mut Field<Ct, Cf>>,
+}
+impl<'f, Ct, Cf> ColumnIterMut<'f, Ct, Cf> {
+    /// Creates an iterator over the specified column of the specified field.
+    ///
+    /// # Panics
+    /// Panics if the specified column is out of range.
+    #[inline]
+    pub fn new(field: &'f mut Field<Ct, Cf>, column: usize) -> Self {
+        assert!(column < field.dimensions()[0].get());
+        let [width, height] = field.dimensions();
+        Self {
+            tiles: field.all_tiles_mut().into_slice().as_mut_ptr(),
+            width: width.get(), column, index: 0..height.get(),
+            _field: PhantomData,
+        }
+    }
+    /// Returns the tile at the specified row of the column, which must be in the remaining range of the iterator.
+    #[inline(always)]
+    fn tile(&self, row: usize) -> &'f mut Tile<Ct, Cf> {
+        debug_assert!(self.index.contains(&row) || row == self.index.end);
+        // SAFETY: the pointer is derived from a mutable borrow of the entire storage, which lives for 'f. The row is inside the field, and every row of the column is produced at most once, since the range only shrinks. Different iterators created by FieldColumnsIterMut have different columns, so the references never alias.
+        unsafe {&mut *self.tiles.add(self.column + row * self.width)}
+    }
+}
+impl<'f, Ct, Cf> Iterator for ColumnIterMut<'f, Ct, Cf> {
+    type Item = &'f mut Tile<Ct, Cf>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len() == 0 {
+            return None;
+        }
+        let el = self.tile(self.index.start);
+        self.index.start += 1;
+        Some(el)
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+impl<Ct, Cf> DoubleEndedIterator for ColumnIterMut<'_, Ct, Cf> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len() == 0 {
+            return None;
+        }
+        self.index.end -= 1;
+        Some(self.tile(self.index.end))
+    }
+}
+impl<Ct, Cf> ExactSizeIterator for ColumnIterMut<'_, Ct, Cf> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.index.end - self.index.start
+    }
+}
+impl<Ct, Cf> FusedIterator for ColumnIterMut<'_, Ct, Cf> {}
+// SAFETY: the iterator behaves like a collection of `&mut Tile` references.
+unsafe impl<Ct: Send, Cf: Send> Send for ColumnIterMut<'_, Ct, Cf> {}
+unsafe impl<Ct: Sync, Cf: Sync> Sync for ColumnIterMut<'_, Ct, Cf> {}
+
+/// An iterator over the mutable rows of a field.
+///
+/// # Usage
+/// ```
+/// # use sweeper::Field;
+/// # use core::num::NonZeroUsize;
+/// #
+/// // Store the row-major index of every tile in its payload, row by row.
+/// let mut field = Field::<usize, ()>::empty([NonZeroUsize::new(9).unwrap(), NonZeroUsize::new(4).unwrap()]);
+/// for (y, row) in field.rows_mut().enumerate() {
+///     for (x, tile) in row.enumerate() {
+///         tile.payload = y * 9 + x;
+///     }
+/// }
+/// assert_eq!(field[[4, 2]].payload, 22);
+/// assert!(field.all_tiles().enumerate().all(|(i, tile)| tile.payload == i));
+/// ```
+#[derive(Debug)]
+pub struct FieldRowsIterMut<'f, Ct, Cf> {
+    rows: ChunksMut<'f, Tile<Ct, Cf>>,
+}
+impl<'f, Ct, Cf> FieldRowsIterMut<'f, Ct, Cf> {
+    /// Returns an iterator over the specified field's rows.
+    #[inline]
+    pub fn new(field: &'f mut Field<Ct, Cf>) -> Self {
+        let width = field.dimensions()[0].get();
+        Self {rows: field.all_tiles_mut().into_slice().chunks_mut(width)}
+    }
+}
+impl<'f, Ct, Cf> Iterator for FieldRowsIterMut<'f, Ct, Cf> {
+    type Item = RowIterMut<'f, Ct, Cf>;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows.next().map(|row| RowIterMut {tiles: row.iter_mut()})
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+impl<Ct, Cf> DoubleEndedIterator for FieldRowsIterMut<'_, Ct, Cf> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.rows.next_back().map(|row| RowIterMut {tiles: row.iter_mut()})
+    }
+}
+impl<Ct, Cf> ExactSizeIterator for FieldRowsIterMut<'_, Ct, Cf> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.rows.len()
+    }
+}
+impl<Ct, Cf> FusedIterator for FieldRowsIterMut<'_, Ct, Cf> {}
+
+/// An iterator over the mutable columns of a field.
+///
+/// # Usage
+/// ```
+/// # use sweeper::Field;
+/// # use core::num::NonZeroUsize;
+/// #
+/// // Store the column-major index of every tile in its payload, column by column.
+/// let mut field = Field::<usize, ()>::empty([NonZeroUsize::new(9).unwrap(), NonZeroUsize::new(8).unwrap()]);
+/// let mut columns: Vec<_> = field.columns_mut().collect(); // All columns can be borrowed at once.
+/// for (x, column) in columns.iter_mut().enumerate().rev() {
+///     for (y, tile) in column.enumerate() {
+///         tile.payload = x * 8 + y;
+///     }
+/// }
+/// assert_eq!(field[[8, 7]].payload, 71);
+/// assert_eq!(field[[1, 0]].payload, 8);
+/// ```
+#[derive(Debug)]
+pub struct FieldColumnsIterMut<'f, Ct, Cf> {
+    tiles: *mut Tile<Ct, Cf>,
+    dimensions: FieldDimensions,
+    index: Range<usize>,
+    _field: PhantomData<&'f mut Field<Ct, Cf>>,
+}
+impl<'f, Ct, Cf> FieldColumnsIterMut<'f, Ct, Cf> {
+    /// Returns an iterator over the specified field's columns.
+    #[inline]
+    pub fn new(field: &'f mut Field<Ct, Cf>) -> Self {
+        let dimensions = field.dimensions();
+        Self {
+            tiles: field.all_tiles_mut().into_slice().as_mut_ptr(),
+            dimensions, index: 0..dimensions[0].get(),
+            _field: PhantomData,
+        }
+    }
+    /// Creates an iterator over the specified column, which must not have been produced before.
+    #[inline(always)]
+    fn column(&self, column: usize) -> ColumnIterMut<'f, Ct, Cf> {
+        ColumnIterMut {
+            tiles: self.tiles,
+            width: self.dimensions[0].get(), column, index: 0..self.dimensions[1].get(),
+            _field: PhantomData,
+        }
+    }
+}
+impl<'f, Ct, Cf> Iterator for FieldColumnsIterMut<'f, Ct, Cf> {
+    type Item = ColumnIterMut<'f, Ct, Cf>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len() == 0 {
+            return None;
+        }
+        let el = self.column(self.index.start);
+        self.index.start += 1;
+        Some(el)
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+impl<Ct, Cf> DoubleEndedIterator for FieldColumnsIterMut<'_, Ct, Cf> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len() == 0 {
+            return None;
+        }
+        self.index.end -= 1;
+        Some(self.column(self.index.end))
+    }
+}
+impl<Ct, Cf> ExactSizeIterator for FieldColumnsIterMut<'_, Ct, Cf> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.index.end - self.index.start
+    }
+}
+impl<Ct, Cf> FusedIterator for FieldColumnsIterMut<'_, Ct, Cf> {}
+// SAFETY: the iterator behaves like a collection of `&mut Tile` references.
+unsafe impl<Ct: Send, Cf: Send> Send for FieldColumnsIterMut<'_, Ct, Cf> {}
+unsafe impl<Ct: Sync, Cf: Sync> Sync for FieldColumnsIterMut<'_, Ct, Cf> {}
+
+/// Returns the location of the neighbor of the specified tile with the specified index in the order of the neighborhood, which is also the order used by `ChordOutcome`, or `None` if the neighbor is outside the field.
+///
+/// On narrow wrapping fields, several offsets can lead to the same tile or back to the center. Such a tile is only a neighbor for the first offset leading to it, and the center is never its own neighbor.
+pub(crate) fn neighbor(dimensions: FieldDimensions, topology: Topology, neighborhood: Neighborhood, center: FieldCoordinates, index: usize) -> Option<FieldCoordinates> {
+    let location = topology.offset(dimensions, center, neighborhood.offset(index)?)?;
+    if topology != Topology::Bounded {
+        let seen_before = neighborhood.offsets()
+            .take(index)
+            .any(|offset| topology.offset(dimensions, center, offset) == Some(location));
+        if location == center || seen_before {return None}
+    }
+    Some(location)
+}
+
+/// Iterates over the locations of the neighbors of a tile.
+///
+/// By default, the neighbors are the directly and diagonally adjacent tiles, produced in the same clockwise order as the entries of a [`ChordOutcome`][chord_outcome], starting from top-left: ↖, ↑, ↗, →, ↘, ↓, ↙, ←. Neighbors which would be outside the field are skipped, which makes this the safe way to look around tiles on the border of the field. Since the iterator only stores the dimensions of the field, it doesn't borrow the field and can be used while modifying it. The [`orthogonal`][m_orthogonal] constructor only produces the directly adjacent neighbors, in the same order: ↑, →, ↓, ←, and [`with_neighborhood`][m_with_neighborhood] switches to an arbitrary [neighborhood][neighborhood].
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Field, NeighborLocations};
+/// # use core::num::NonZeroUsize;
+/// #
+/// let field = Field::<(), ()>::empty([NonZeroUsize::new(3).unwrap(); 2]);
+/// // The top-left corner only has 3 neighbors.
+/// let neighbors: Vec<_> = field.neighbor_locations([0, 0]).collect();
+/// assert_eq!(neighbors, [[1, 0], [1, 1], [0, 1]]);
+///
+/// // Check every tile of every field up to 4x4 against the definition.
+/// for width in 1..=4 {
+///     for height in 1..=4 {
+///         let dimensions = [NonZeroUsize::new(width).unwrap(), NonZeroUsize::new(height).unwrap()];
+///         for y in 0..height as isize {
+///             for x in 0..width as isize {
+///                 let expected: Vec<_> = [[-1, -1], [0, -1], [1, -1], [1, 0], [1, 1], [0, 1], [-1, 1], [-1, 0]]
+///                     .iter()
+///                     .map(|[dx, dy]| [x + dx, y + dy])
+///                     .filter(|&[nx, ny]| nx >= 0 && ny >= 0 && nx < width as isize && ny < height as isize)
+///                     .map(|[nx, ny]| [nx as usize, ny as usize])
+///                     .collect();
+///                 let actual: Vec<_> = NeighborLocations::new(dimensions, [x as usize, y as usize]).collect();
+///                 assert_eq!(actual, expected);
+///             }
+///         }
+///         // Tiles outside the field have no neighbors.
+///         assert_eq!(NeighborLocations::new(dimensions, [width, 0]).count(), 0);
+///         assert_eq!(NeighborLocations::new(dimensions, [0, height]).count(), 0);
+///     }
+/// }
+/// ```
+///
+/// [chord_outcome]: ../type.ChordOutcome.html "ChordOutcome — the outcome of a chord operation"
+/// [m_orthogonal]: #method.orthogonal "orthogonal — creates an iterator over the directly adjacent neighbors of the specified tile"
+/// [m_with_neighborhood]: #method.with_neighborhood "with_neighborhood — makes the iterator produce the neighbors according to the specified neighborhood"
+/// [neighborhood]: ../struct.Neighborhood.html "Neighborhood — describes which tiles around a tile count as its neighbors"
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NeighborLocations {
+    dimensions: FieldDimensions,
+    topology: Topology,
+    neighborhood: Neighborhood,
+    center: FieldCoordinates,
+    index: usize,
+}
+impl NeighborLocations {
+    /// Creates an iterator over the neighbors of the specified tile on a field with the specified dimensions. If the tile itself is outside the field, the iterator is empty.
+    #[inline]
+    pub const fn new(dimensions: FieldDimensions, center: FieldCoordinates) -> Self {
+        let inside = center[0] < dimensions[0].get() && center[1] < dimensions[1].get();
+        Self {dimensions, topology: Topology::Bounded, neighborhood: Neighborhood::MOORE, center, index: if inside {0} else {Neighborhood::MAX_SIZE}}
+    }
+    /// Creates an iterator over the directly adjacent neighbors of the specified tile on a field with the specified dimensions, skipping the diagonal ones. If the tile itself is outside the field, the iterator is empty.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::NeighborLocations;
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let dimensions = [NonZeroUsize::new(3).unwrap(); 2];
+    /// let neighbors: Vec<_> = NeighborLocations::orthogonal(dimensions, [1, 1]).collect();
+    /// assert_eq!(neighbors, [[1, 0], [2, 1], [1, 2], [0, 1]]);
+    /// let neighbors: Vec<_> = NeighborLocations::orthogonal(dimensions, [2, 0]).collect();
+    /// assert_eq!(neighbors, [[2, 1], [1, 0]]);
+    /// ```
+    #[inline]
+    pub const fn orthogonal(dimensions: FieldDimensions, center: FieldCoordinates) -> Self {
+        Self::new(dimensions, center).with_neighborhood(Neighborhood::VON_NEUMANN)
+    }
+    /// Makes the iterator look for the neighbors on a field with the specified [topology][topology] instead of a bounded one.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{NeighborLocations, Topology};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let dimensions = [NonZeroUsize::new(3).unwrap(); 2];
+    /// let neighbors: Vec<_> = NeighborLocations::orthogonal(dimensions, [0, 0])
+    ///     .with_topology(Topology::Cylindrical)
+    ///     .collect();
+    /// assert_eq!(neighbors, [[1, 0], [0, 1], [2, 0]]);
+    /// ```
+    ///
+    /// [topology]: ../enum.Topology.html "Topology — describes which edges of a field touch each other"
+    #[inline(always)]
+    #[must_use = "this returns a new iterator instead of modifying the existing one"]
+    pub const fn with_topology(self, topology: Topology) -> Self {
+        Self {topology, ..self}
+    }
+    /// Makes the iterator produce the neighbors according to the specified [neighborhood][neighborhood] instead of the directly and diagonally adjacent ones.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{NeighborLocations, Neighborhood};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let dimensions = [NonZeroUsize::new(3).unwrap(); 2];
+    /// let neighbors: Vec<_> = NeighborLocations::new(dimensions, [0, 0])
+    ///     .with_neighborhood(Neighborhood::KNIGHT)
+    ///     .collect();
+    /// assert_eq!(neighbors, [[2, 1], [1, 2]]);
+    /// ```
+    ///
+    /// [neighborhood]: ../struct.Neighborhood.html "Neighborhood — describes which tiles around a tile count as its neighbors"
+    #[inline(always)]
+    #[must_use = "this returns a new iterator instead of modifying the existing one"]
+    pub const fn with_neighborhood(self, neighborhood: Neighborhood) -> Self {
+        Self {neighborhood, ..self}
+    }
+    /// Returns the location of the tile whose neighbors are being iterated over.
+    #[inline(always)]
+    pub const fn center(&self) -> FieldCoordinates {
+        self.center
+    }
+}
+impl Iterator for NeighborLocations {
+    type Item = FieldCoordinates;
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.neighborhood.len() {
+            let index = self.index;
+            self.index += 1;
+            if let Some(location) = neighbor(self.dimensions, self.topology, self.neighborhood, self.center, index) {
+                return Some(location);
+            }
+        }
+        None
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.neighborhood.len().saturating_sub(self.index)))
+    }
+}
+impl FusedIterator for NeighborLocations {}
+
+/// Iterates over the neighbors of a tile along with their locations.
+///
+/// The neighbors are produced in the same order as by [`NeighborLocations`][nl], skipping the ones outside the field.
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Field, TileState, Flag};
+/// # use core::num::NonZeroUsize;
+/// #
+/// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(3).unwrap(); 2]);
+/// field[[2, 0]].state = TileState::Mine(Flag::Flagged);
+/// field[[2, 1]].state = TileState::Mine(Flag::NotFlagged);
+/// let flagged: Vec<_> = field.neighbors([1, 1])
+///     .filter(|(_, tile)| tile.state.is_flagged())
+///     .map(|(location, _)| location)
+///     .collect();
+/// assert_eq!(flagged, [[2, 0]]);
+/// // The mine on the diagonal isn't an orthogonal neighbor.
+/// assert_eq!(field.orthogonal_neighbors([1, 2]).filter(|(_, tile)| tile.state.is_mine()).count(), 0);
+/// ```
+///
+/// [nl]: struct.NeighborLocations.html "NeighborLocations — iterates over the locations of the neighbors of a tile"
+#[derive(Clone, Debug)]
+pub struct Neighbors<'f, Ct, Cf> {
+    field: &'f Field<Ct, Cf>,
+    locations: NeighborLocations,
+}
+impl<'f, Ct, Cf> Neighbors<'f, Ct, Cf> {
+    /// Creates an iterator over the neighbors of the specified tile according to the neighborhood of the field. If the tile itself is outside the field, the iterator is empty.
+    #[inline(always)]
+    pub const fn new(field: &'f Field<Ct, Cf>, center: FieldCoordinates) -> Self {
+        Self {field, locations: field.neighbor_locations(center)}
+    }
+    /// Creates an iterator over the directly adjacent neighbors of the specified tile. If the tile itself is outside the field, the iterator is empty.
+    #[inline(always)]
+    pub const fn orthogonal(field: &'f Field<Ct, Cf>, center: FieldCoordinates) -> Self {
+        Self {field, locations: NeighborLocations::orthogonal(field.dimensions(), center).with_topology(field.topology())}
+    }
+    /// Returns the location of the tile whose neighbors are being iterated over.
+    #[inline(always)]
+    pub const fn center(&self) -> FieldCoordinates {
+        self.locations.center()
+    }
+    /// Returns the field which the iterator iterates over.
+    #[inline(always)]
+    pub const fn field(&self) -> &'f Field<Ct, Cf> {
+        self.field
+    }
+}
+impl<'f, Ct, Cf> Iterator for Neighbors<'f, Ct, Cf> {
+    type Item = (FieldCoordinates, &'f Tile<Ct, Cf>);
+    fn next(&mut self) -> Option<Self::Item> {
+        let location = self.locations.next()?;
+        Some((location, &self.field[location]))
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.locations.size_hint()
+    }
+}
+impl<Ct, Cf> FusedIterator for Neighbors<'_, Ct, Cf> {}
+
+/// Iterates over mutable references to the neighbors of a tile along with their locations.
+///
+/// The neighbors are produced in the same order as by [`NeighborLocations`][nl], skipping the ones outside the field.
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Field, TileState, Flag};
+/// # use core::num::NonZeroUsize;
+/// #
+/// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(3).unwrap(); 2]);
+/// // Flag every neighbor of the bottom left corner.
+/// for (_, tile) in field.neighbors_mut([0, 2]) {
+///     tile.state = TileState::ClosedEmpty(Flag::Flagged);
+/// }
+/// assert_eq!(field.all_tiles().filter(|tile| tile.state.is_flagged()).count(), 3);
+/// assert!(field[[1, 1]].state.is_flagged());
+/// ```
+///
+/// [nl]: struct.NeighborLocations.html "NeighborLocations — iterates over the locations of the neighbors of a tile"
+#[derive(Debug)]
+pub struct NeighborsMut<'f, Ct, Cf> {
+    center: FieldCoordinates,
+    neighbors: [Option<(FieldCoordinates, &'f mut Tile<Ct, Cf>)>; Neighborhood::MAX_SIZE],
+    index: usize,
+}
+impl<'f, Ct, Cf> NeighborsMut<'f, Ct, Cf> {
+    /// Creates an iterator over the neighbors of the specified tile according to the neighborhood of the field. If the tile itself is outside the field, the iterator is empty.
+    #[inline]
+    pub fn new(field: &'f mut Field<Ct, Cf>, center: FieldCoordinates) -> Self {
+        let locations = field.neighbor_locations(center);
+        Self::from_locations(field, locations)
+    }
+    /// Creates an iterator over the directly adjacent neighbors of the specified tile. If the tile itself is outside the field, the iterator is empty.
+    #[inline]
+    pub fn orthogonal(field: &'f mut Field<Ct, Cf>, center: FieldCoordinates) -> Self {
+        let locations = NeighborLocations::orthogonal(field.dimensions(), center).with_topology(field.topology());
+        Self::from_locations(field, locations)
+    }
+    fn from_locations(field: &'f mut Field<Ct, Cf>, locations: NeighborLocations) -> Self {
+        let center = locations.center();
+        let width = field.dimensions()[0].get();
+        // The neighbors are all different tiles, so the storage can be split into separate references to them by walking it in the order of the indices.
+        let mut order = [(0_usize, 0_usize); Neighborhood::MAX_SIZE];
+        let mut count = 0;
+        for (slot, location) in locations.enumerate() {
+            order[slot] = (location[0] + location[1] * width, slot);
+            count += 1;
+        }
+        order[..count].sort_unstable();
+        let mut neighbors: [Option<(FieldCoordinates, &'f mut Tile<Ct, Cf>)>; Neighborhood::MAX_SIZE] = core::array::from_fn(|_| None);
+        let mut rest = field.all_tiles_mut().into_slice();
+        let mut rest_start = 0;
+        for &(index, slot) in &order[..count] {
+            let (tile, tail) = rest.split_at_mut(index - rest_start).1.split_first_mut()
+                .expect("neighbors are inside the field and different from each other");
+            neighbors[slot] = Some(([index % width, index / width], tile));
+            rest = tail;
+            rest_start = index + 1;
+        }
+        Self {center, neighbors, index: 0}
+    }
+    /// Returns the location of the tile whose neighbors are being iterated over.
+    #[inline(always)]
+    pub const fn center(&self) -> FieldCoordinates {
+        self.center
+    }
+}
+impl<'f, Ct, Cf> Iterator for NeighborsMut<'f, Ct, Cf> {
+    type Item = (FieldCoordinates, &'f mut Tile<Ct, Cf>);
+    fn next(&mut self) -> Option<Self::Item> {
+        let neighbor = self.neighbors.get_mut(self.index)?.take()?;
+        self.index += 1;
+        Some(neighbor)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.neighbors[self.index..].iter()
+            .take_while(|neighbor| neighbor.is_some())
+            .count();
+        (remaining, Some(remaining))
+    }
+}
+impl<Ct, Cf> ExactSizeIterator for NeighborsMut<'_, Ct, Cf> {}
+impl<Ct, Cf> FusedIterator for NeighborsMut<'_, Ct, Cf> {}
+
+/// Iterates over all tiles of a field along with their locations, in row-major order.
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Field, TileState, Flag};
+/// # use core::num::NonZeroUsize;
+/// #
+/// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(4).unwrap(), NonZeroUsize::new(3).unwrap()]);
+/// field[[3, 1]].state = TileState::Mine(Flag::NotFlagged);
+/// let (location, _) = field.enumerate_tiles().find(|(_, tile)| tile.state.is_mine()).unwrap();
+/// assert_eq!(location, [3, 1]);
+/// assert_eq!(field.enumerate_tiles().nth(5).unwrap().0, [1, 1]);
+/// assert_eq!(field.enumerate_tiles().next_back().unwrap().0, [3, 2]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct EnumerateTiles<'f, Ct, Cf> {
+    tiles: Enumerate<Iter<'f, Tile<Ct, Cf>>>,
+    width: usize,
+}
+impl<'f, Ct, Cf> EnumerateTiles<'f, Ct, Cf> {
+    /// Creates an iterator over all tiles of the specified field.
+    #[inline]
+    pub fn new(field: &'f Field<Ct, Cf>) -> Self {
+        Self {tiles: field.all_tiles().enumerate(), width: field.dimensions()[0].get()}
+    }
+}
+impl<'f, Ct, Cf> Iterator for EnumerateTiles<'f, Ct, Cf> {
+    type Item = (FieldCoordinates, &'f Tile<Ct, Cf>);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, tile) = self.tiles.next()?;
+        Some(([index % self.width, index / self.width], tile))
+    }
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let (index, tile) = self.tiles.nth(n)?;
+        Some(([index % self.width, index / self.width], tile))
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+impl<Ct, Cf> DoubleEndedIterator for EnumerateTiles<'_, Ct, Cf> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (index, tile) = self.tiles.next_back()?;
+        Some(([index % self.width, index / self.width], tile))
+    }
+}
+impl<Ct, Cf> ExactSizeIterator for EnumerateTiles<'_, Ct, Cf> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.tiles.len()
+    }
+}
+impl<Ct, Cf> FusedIterator for EnumerateTiles<'_, Ct, Cf> {}
+
+/// Iterates over mutable references to all tiles of a field along with their locations, in row-major order.
+///
+/// # Usage
+/// ```
+/// # use sweeper::Field;
+/// # use core::num::NonZeroUsize;
+/// #
+/// // Store the location of every tile in its payload.
+/// let mut field = Field::<[usize; 2], ()>::empty([NonZeroUsize::new(4).unwrap(), NonZeroUsize::new(3).unwrap()]);
+/// for (location, tile) in field.enumerate_tiles_mut() {
+///     tile.payload = location;
+/// }
+/// assert_eq!(field[[2, 1]].payload, [2, 1]);
+/// ```
+#[derive(Debug)]
+pub struct EnumerateTilesMut<'f, Ct, Cf> {
+    tiles: Enumerate<IterMut<'f, Tile<Ct, Cf>>>,
+    width: usize,
+}
+impl<'f, Ct, Cf> EnumerateTilesMut<'f, Ct, Cf> {
+    /// Creates an iterator over all tiles of the specified field.
+    #[inline]
+    pub fn new(field: &'f mut Field<Ct, Cf>) -> Self {
+        let width = field.dimensions()[0].get();
+        Self {tiles: field.all_tiles_mut().enumerate(), width}
+    }
+}
+impl<'f, Ct, Cf> Iterator for EnumerateTilesMut<'f, Ct, Cf> {
+    type Item = (FieldCoordinates, &'f mut Tile<Ct, Cf>);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, tile) = self.tiles.next()?;
+        Some(([index % self.width, index / self.width], tile))
+    }
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let (index, tile) = self.tiles.nth(n)?;
+        Some(([index % self.width, index / self.width], tile))
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+impl<Ct, Cf> DoubleEndedIterator for EnumerateTilesMut<'_, Ct, Cf> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (index, tile) = self.tiles.next_back()?;
+        Some(([index % self.width, index / self.width], tile))
+    }
+}
+impl<Ct, Cf> ExactSizeIterator for EnumerateTilesMut<'_, Ct, Cf> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.tiles.len()
+    }
+}
+impl<Ct, Cf> FusedIterator for EnumerateTilesMut<'_, Ct, Cf> {}
+
+/// Iterates over the tiles of a field which are in a certain state, along with their locations, in row-major order.
+///
+/// Created by [`mines`][m_mines], [`closed_tiles`][m_closed_tiles], [`flagged_tiles`][m_flagged_tiles] and [`open_numbers`][m_open_numbers].
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Field, TileState, Flag};
+/// # use core::num::{NonZeroUsize, NonZeroU8};
+/// #
+/// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(3).unwrap(); 2]);
+/// field[[0, 0]].state = TileState::Mine(Flag::Flagged);
+/// field[[2, 2]].state = TileState::Mine(Flag::NotFlagged);
+/// field[[1, 0]].state = TileState::OpenNumber(NonZeroU8::new(1).unwrap());
+/// field[[2, 0]].state = TileState::ClosedEmpty(Flag::Flagged);
+///
+/// let mines: Vec<_> = field.mines().map(|(location, _)| location).collect();
+/// assert_eq!(mines, [[0, 0], [2, 2]]);
+/// let flags: Vec<_> = field.flagged_tiles().map(|(location, _)| location).collect();
+/// assert_eq!(flags, [[0, 0], [2, 0]]);
+/// assert_eq!(field.closed_tiles().count(), 8);
+/// assert_eq!(field.open_numbers().next().unwrap().0, [1, 0]);
+/// ```
+///
+/// [m_mines]: ../struct.Field.html#method.mines "mines — returns an iterator over the tiles with mines"
+/// [m_closed_tiles]: ../struct.Field.html#method.closed_tiles "closed_tiles — returns an iterator over the closed tiles"
+/// [m_flagged_tiles]: ../struct.Field.html#method.flagged_tiles "flagged_tiles — returns an iterator over the flagged tiles"
+/// [m_open_numbers]: ../struct.Field.html#method.open_numbers "open_numbers — returns an iterator over the open tiles with numbers"
+#[derive(Clone)]
+pub struct FilteredTiles<'f, Ct, Cf> {
+    tiles: EnumerateTiles<'f, Ct, Cf>,
+    predicate: fn(&TileState<Cf>) -> bool,
+}
+impl<'f, Ct, Cf> FilteredTiles<'f, Ct, Cf> {
+    /// Creates an iterator over the tiles of the specified field whose state satisfies the specified predicate.
+    #[inline]
+    pub fn new(field: &'f Field<Ct, Cf>, predicate: fn(&TileState<Cf>) -> bool) -> Self {
+        Self {tiles: EnumerateTiles::new(field), predicate}
+    }
+}
+impl<'f, Ct, Cf> Iterator for FilteredTiles<'f, Ct, Cf> {
+    type Item = (FieldCoordinates, &'f Tile<Ct, Cf>);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let predicate = self.predicate;
+        self.tiles.find(|(_, tile)| predicate(&tile.state))
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.tiles.len()))
+    }
+}
+impl<Ct, Cf> DoubleEndedIterator for FilteredTiles<'_, Ct, Cf> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let predicate = self.predicate;
+        self.tiles.rfind(|(_, tile)| predicate(&tile.state))
+    }
+}
+impl<Ct, Cf> FusedIterator for FilteredTiles<'_, Ct, Cf> {}
+// Deriving this requires the predicate to implement Debug for all lifetimes, which older compilers can't prove.
+impl<Ct: fmt::Debug, Cf: fmt::Debug> fmt::Debug for FilteredTiles<'_, Ct, Cf> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FilteredTiles")
+            .field("tiles", &self.tiles)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,189 @@
+//! Undo and redo of player actions.
+
+use alloc::vec::Vec;
+#[cfg(feature = "serialization")]
+use serde::{Serialize, Deserialize};
+use crate::{Field, FieldCoordinates, TileState};
+
+/// A player action recorded in a [journal][journal].
+///
+/// [journal]: struct.Journal.html "Journal — a history of player actions which can be undone and redone"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub enum Action {
+    /// A tile was opened.
+    Open(FieldCoordinates),
+    /// The flag on a tile was changed.
+    SetFlag(FieldCoordinates),
+    /// A chord was performed on a tile.
+    Chord(FieldCoordinates),
+    /// A recursive chord was performed on a tile.
+    RecursiveChord(FieldCoordinates),
+}
+impl Action {
+    /// Returns the coordinates of the tile the action was performed on.
+    #[inline]
+    pub const fn location(self) -> FieldCoordinates {
+        match self {
+            Self::Open(location)
+          | Self::SetFlag(location)
+          | Self::Chord(location)
+          | Self::RecursiveChord(location) => location,
+        }
+    }
+}
+
+/// The change of the state of a single tile caused by an action.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct TileChange<Cf> {
+    /// The coordinates of the tile which was changed.
+    pub location: FieldCoordinates,
+    /// The state of the tile before the action.
+    pub before: TileState<Cf>,
+    /// The state of the tile after the action.
+    pub after: TileState<Cf>,
+}
+
+/// An action together with all the tile changes it caused, in the order they were made.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct JournalEntry<Cf> {
+    /// The action which was performed.
+    pub action: Action,
+    /// The changes caused by the action. Can be empty if the action didn't change any tiles, like opening a mine.
+    pub changes: Vec<TileChange<Cf>>,
+}
+impl<Cf: Clone> JournalEntry<Cf> {
+    /// Sets all changed tiles on the field to their state after the action.
+    ///
+    /// # Panics
+    /// Panics if any of the changed tiles is out of bounds.
+    pub fn apply<Ct>(&self, field: &mut Field<Ct, Cf>) {
+        for change in &self.changes {
+            field[change.location].state = change.after.clone();
+        }
+    }
+    /// Sets all changed tiles on the field to their state before the action.
+    ///
+    /// # Panics
+    /// Panics if any of the changed tiles is out of bounds.
+    pub fn revert<Ct>(&self, field: &mut Field<Ct, Cf>) {
+        for change in self.changes.iter().rev() {
+            field[change.location].state = change.before.clone();
+        }
+    }
+}
+
+/// A history of player actions which can be undone and redone.
+///
+/// Every entry stores only the tiles changed by the action, which makes undoing and redoing as expensive as the action itself rather than the whole field. Recording a new entry discards the entries which were undone before, just like in a text editor. The journal doesn't perform actions by itself — it's filled by a [`Game`][game], or by a frontend which implements its own rules on top of a [`Field`][field].
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Field, TileState, Flag, Journal, JournalEntry, TileChange, Action};
+/// # use core::num::NonZeroUsize;
+/// #
+/// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(3).unwrap(); 2]);
+/// let mut journal = Journal::new();
+///
+/// field[[1, 1]].state = TileState::ClosedEmpty(Flag::Flagged);
+/// journal.record(JournalEntry {
+///     action: Action::SetFlag([1, 1]),
+///     changes: vec![TileChange {
+///         location: [1, 1],
+///         before: TileState::ClosedEmpty(Flag::NotFlagged),
+///         after: TileState::ClosedEmpty(Flag::Flagged),
+///     }],
+/// });
+///
+/// assert_eq!(journal.undo(&mut field).map(|entry| entry.action), Some(Action::SetFlag([1, 1])));
+/// assert!(!field[[1, 1]].state.is_flagged());
+/// journal.redo(&mut field);
+/// assert!(field[[1, 1]].state.is_flagged());
+/// ```
+///
+/// [game]: struct.Game.html "Game — a Minesweeper game session"
+/// [field]: struct.Field.html "Field — a playfield"
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct Journal<Cf> {
+    entries: Vec<JournalEntry<Cf>>,
+    position: usize,
+}
+impl<Cf> Journal<Cf> {
+    /// Creates an empty journal.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {entries: Vec::new(), position: 0}
+    }
+    /// Adds an entry for an action which has already been performed, discarding all undone entries.
+    #[inline]
+    pub fn record(&mut self, entry: JournalEntry<Cf>) {
+        self.entries.truncate(self.position);
+        self.entries.push(entry);
+        self.position += 1;
+    }
+    /// Returns the entries which are currently in effect, i.e. the ones which were recorded and not undone, from the oldest to the newest.
+    #[inline]
+    pub fn done(&self) -> &[JournalEntry<Cf>] {
+        &self.entries[..self.position]
+    }
+    /// Returns the entries which were undone and can be redone, from the oldest to the newest.
+    #[inline]
+    pub fn undone(&self) -> &[JournalEntry<Cf>] {
+        &self.entries[self.position..]
+    }
+    /// Returns the amount of entries in effect, which is also the amount of times `undo` can be called successfully.
+    #[inline(always)]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+    /// Returns `true` if there is an entry to undo, `false` otherwise.
+    #[inline(always)]
+    pub const fn can_undo(&self) -> bool {
+        self.position > 0
+    }
+    /// Returns `true` if there is an entry to redo, `false` otherwise.
+    #[inline(always)]
+    pub fn can_redo(&self) -> bool {
+        self.position < self.entries.len()
+    }
+    /// Removes all entries, making the current state of the field the start of the history.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.position = 0;
+    }
+}
+impl<Cf: Clone> Journal<Cf> {
+    /// Reverts the newest entry in effect on the specified field and returns it, or returns `None` if there's nothing to undo.
+    ///
+    /// # Panics
+    /// Panics if the entry refers to tiles outside the field, which only happens if the journal is used with a different field than the one it was recorded for.
+    pub fn undo<Ct>(&mut self, field: &mut Field<Ct, Cf>) -> Option<&JournalEntry<Cf>> {
+        if !self.can_undo() {return None}
+        self.position -= 1;
+        let entry = &self.entries[self.position];
+        entry.revert(field);
+        Some(entry)
+    }
+    /// Applies the oldest undone entry on the specified field and returns it, or returns `None` if there's nothing to redo.
+    ///
+    /// # Panics
+    /// Panics if the entry refers to tiles outside the field, which only happens if the journal is used with a different field than the one it was recorded for.
+    pub fn redo<Ct>(&mut self, field: &mut Field<Ct, Cf>) -> Option<&JournalEntry<Cf>> {
+        if !self.can_redo() {return None}
+        let entry = &self.entries[self.position];
+        self.position += 1;
+        entry.apply(field);
+        Some(entry)
+    }
+}
+impl<Cf> Default for Journal<Cf> {
+    /// Creates an empty journal.
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -9,7 +9,7 @@
 //!
 //! - `generation` — enable random generation (**enabled by default**)
 //!
-//!   Enables the dependency on `rand`, used for generating random fields. Disable to remove said dependency if you'd like to use your own random field generator.
+//!   Enables the dependency on `rand` and `rand_pcg`, used for generating random fields. Disable to remove said dependencies if you'd like to use your own random field generator. Generation with a custom random number generator or from a seed is available without the `std` feature, while generation using the thread-local random number generator requires it.
 //!
 //! - `serialization` — enable support for field serialization (**enabled by default**, disable to speed up compilation)
 //!
@@ -39,9 +39,38 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
 
+pub mod binary;
+pub use binary::*;
+pub mod cube;
+pub use cube::*;
+mod error;
+pub use error::*;
 mod field;
 pub use field::*;
+mod game;
+pub use game::*;
+#[cfg(feature = "generation")]
+mod generation;
+#[cfg(feature = "generation")]
+pub use generation::*;
+mod grid;
+pub mod hex;
+pub use hex::*;
 pub mod iter;
 pub use iter::*;
+mod journal;
+pub use journal::*;
+mod neighborhood;
+pub use neighborhood::*;
+pub mod solver;
+pub mod probability;
+mod replay;
+pub use replay::*;
+pub mod share;
+pub use share::*;
+pub mod text;
+pub use text::*;
 mod tile;
-pub use tile::*;
\ No newline at end of file
+pub use tile::*;
+mod topology;
+pub use topology::*;
\ No newline at end of file
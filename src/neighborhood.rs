@@ -0,0 +1,223 @@
+//! The shape of the area around a tile in which the mines are counted.
+
+use core::fmt::{self, Formatter};
+#[cfg(feature = "serialization")]
+use alloc::vec::Vec;
+#[cfg(feature = "serialization")]
+use serde::{
+    Serialize, Deserialize,
+    ser::{Serializer, SerializeSeq},
+    de::Deserializer,
+};
+
+/// The largest distance from the center along either axis which a neighborhood can cover.
+const MAX_RADIUS: isize = 3;
+/// The offsets which can be a part of a neighborhood, ring by ring from the inside out. Every ring is walked clockwise, starting from its top left corner, so the first 8 offsets are the ones of the Moore neighborhood in the order of `ChordOutcome`: ↖, ↑, ↗, →, ↘, ↓, ↙, ←.
+const RING_OFFSETS: [[isize; 2]; Neighborhood::MAX_SIZE] = {
+    let mut offsets = [[0_isize; 2]; Neighborhood::MAX_SIZE];
+    let mut i = 0;
+    let mut radius = 1;
+    while radius <= MAX_RADIUS {
+        let [mut x, mut y] = [-radius, -radius];
+        let mut step = 0;
+        while step < 8 * radius {
+            offsets[i] = [x, y];
+            i += 1;
+            // Go right along the top side, down along the right one, left along the bottom one and up along the left one.
+            match step / (2 * radius) {
+                0 => x += 1,
+                1 => y += 1,
+                2 => x -= 1,
+                _ => y -= 1,
+            }
+            step += 1;
+        }
+        radius += 1;
+    }
+    offsets
+};
+
+/// Describes which tiles around a tile count as its neighbors.
+///
+/// The neighborhood of a field decides everything which looks at the neighbors of a tile: the numbers on open tiles, chords, clearings, the 3BV value, the safe zone around the first click and the [neighbor iterators][nl]. Classic Minesweeper uses the [Moore neighborhood][moore], which is returned by the `Default` trait implementation, while variant puzzles count mines under knight moves, only orthogonally or in bigger squares.
+///
+/// A neighborhood is a set of offsets from the center tile, each of which is at most 3 tiles away along both axes, which means that a tile can have at most [`MAX_SIZE`][max_size] neighbors. The offsets are always ordered ring by ring from the inside out, with every ring walked clockwise from its top left corner; this is the order in which the neighbors are produced and in which the entries of a [`ChordOutcome`][co] are stored. Just like with the [topology][topology], only the Serde format stores the neighborhood of a field.
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Field, TileState, Flag, Neighborhood};
+/// # use core::num::NonZeroUsize;
+/// #
+/// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(5).unwrap(); 2])
+///     .with_neighborhood(Neighborhood::KNIGHT);
+/// field[[2, 2]].state = TileState::Mine(Flag::NotFlagged);
+/// // The mine is a knight's move away from the corner, but not from the tile next to it.
+/// assert_eq!(field.count_neighboring_mines([0, 1]), 1);
+/// assert_eq!(field.count_neighboring_mines([1, 1]), 0);
+///
+/// let field = field.with_neighborhood(Neighborhood::square(2).unwrap());
+/// assert_eq!(field.count_neighboring_mines([0, 0]), 1);
+/// assert_eq!(field.neighbors([2, 2]).count(), 24);
+/// ```
+///
+/// [nl]: iter/struct.NeighborLocations.html "NeighborLocations — iterates over the locations of the neighbors of a tile"
+/// [moore]: #associatedconstant.MOORE "MOORE — the directly and diagonally adjacent tiles"
+/// [max_size]: #associatedconstant.MAX_SIZE "MAX_SIZE — the largest amount of offsets a neighborhood can have"
+/// [co]: type.ChordOutcome.html "ChordOutcome — the outcome of a chord operation"
+/// [topology]: enum.Topology.html "Topology — describes which edges of a field touch each other"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Neighborhood {
+    /// The set of offsets, with every bit corresponding to the offset with the same index in `RING_OFFSETS`.
+    mask: u64,
+}
+impl Neighborhood {
+    /// The largest amount of offsets a neighborhood can have, which is the amount of tiles in a 7x7 square without its center.
+    pub const MAX_SIZE: usize = 48;
+    /// The directly and diagonally adjacent tiles, in the order ↖, ↑, ↗, →, ↘, ↓, ↙, ←. This is the neighborhood of classic Minesweeper.
+    ///
+    /// Returned by the `Default` trait implementation.
+    pub const MOORE: Self = Self {mask: 0xFF};
+    /// The directly adjacent tiles, in the order ↑, →, ↓, ←.
+    pub const VON_NEUMANN: Self = Self {mask: 0xAA};
+    /// The tiles which are a chess knight's move away, clockwise starting from the one two tiles up and one tile left.
+    pub const KNIGHT: Self = Self {mask: 0x00AA_AA00};
+
+    /// Creates a neighborhood which consists of all tiles at most `radius` tiles away along both axes, i.e. a square with a side of `2 × radius + 1` tiles without its center, or returns `None` if the radius is bigger than 3.
+    ///
+    /// The square with a radius of 1 is the [Moore neighborhood][moore], while a radius of 0 gives a neighborhood without any tiles.
+    ///
+    /// [moore]: #associatedconstant.MOORE "MOORE — the directly and diagonally adjacent tiles"
+    pub const fn square(radius: usize) -> Option<Self> {
+        if radius > MAX_RADIUS as usize {return None}
+        // The rings are stored from the inside out, so the square takes up all offsets up to the end of its outermost ring.
+        let size = (2 * radius + 1) * (2 * radius + 1) - 1;
+        Some(Self {mask: (1 << size) - 1})
+    }
+    /// Creates a neighborhood with the specified offsets from the center tile. The order of the offsets doesn't matter, and duplicate offsets are only counted once.
+    ///
+    /// # Errors
+    /// Returns [`CenterIncluded`][e_ci] if one of the offsets is `[0, 0]` and [`OffsetTooFar`][e_otf] if one of the offsets is more than 3 tiles away from the center along one of the axes.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{Neighborhood, NeighborhoodError};
+    /// #
+    /// let orthogonal = Neighborhood::from_offsets(&[[0, 1], [-1, 0], [1, 0], [0, -1]])?;
+    /// assert_eq!(orthogonal, Neighborhood::VON_NEUMANN);
+    /// // The offsets are always produced in the same order.
+    /// assert_eq!(orthogonal.offsets().collect::<Vec<_>>(), [[0, -1], [1, 0], [0, 1], [-1, 0]]);
+    ///
+    /// assert_eq!(Neighborhood::from_offsets(&[[0, 0]]), Err(NeighborhoodError::CenterIncluded));
+    /// assert_eq!(Neighborhood::from_offsets(&[[4, 0]]), Err(NeighborhoodError::OffsetTooFar([4, 0])));
+    /// # Ok::<(), NeighborhoodError>(())
+    /// ```
+    ///
+    /// [e_ci]: enum.NeighborhoodError.html#variant.CenterIncluded "NeighborhoodError::CenterIncluded — the center tile was specified as its own neighbor"
+    /// [e_otf]: enum.NeighborhoodError.html#variant.OffsetTooFar "NeighborhoodError::OffsetTooFar — an offset is too far away from the center"
+    pub fn from_offsets(offsets: &[[isize; 2]]) -> Result<Self, NeighborhoodError> {
+        let mut mask = 0_u64;
+        for &offset in offsets {
+            if offset == [0, 0] {
+                return Err(NeighborhoodError::CenterIncluded);
+            }
+            let index = RING_OFFSETS.iter()
+                .position(|&ring_offset| ring_offset == offset)
+                .ok_or(NeighborhoodError::OffsetTooFar(offset))?;
+            mask |= 1 << index;
+        }
+        Ok(Self {mask})
+    }
+    /// Returns the amount of offsets in the neighborhood, which is the largest amount of neighbors a tile can have.
+    #[inline(always)]
+    pub const fn len(self) -> usize {
+        self.mask.count_ones() as usize
+    }
+    /// Returns `true` if the neighborhood doesn't have any offsets, `false` otherwise.
+    #[inline(always)]
+    pub const fn is_empty(self) -> bool {
+        self.mask == 0
+    }
+    /// Returns `true` if the specified offset is a part of the neighborhood, `false` otherwise.
+    #[inline]
+    pub fn contains(self, offset: [isize; 2]) -> bool {
+        self.offsets().any(|neighborhood_offset| neighborhood_offset == offset)
+    }
+    /// Returns the offset with the specified index in the order of the neighborhood, or `None` if the index is out of range.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::Neighborhood;
+    /// #
+    /// assert_eq!(Neighborhood::MOORE.offset(1), Some([0, -1]));
+    /// assert_eq!(Neighborhood::VON_NEUMANN.offset(1), Some([1, 0]));
+    /// assert_eq!(Neighborhood::VON_NEUMANN.offset(4), None);
+    /// ```
+    pub const fn offset(self, index: usize) -> Option<[isize; 2]> {
+        let mut mask = self.mask;
+        let mut skipped = 0;
+        while skipped < index && mask != 0 {
+            mask &= mask - 1; // Clear the lowest set bit.
+            skipped += 1;
+        }
+        if mask == 0 {return None}
+        Some(RING_OFFSETS[mask.trailing_zeros() as usize])
+    }
+    /// Returns an iterator over the offsets of the neighborhood, in its order.
+    pub fn offsets(self) -> impl Iterator<Item = [isize; 2]> + Clone {
+        RING_OFFSETS.iter()
+            .enumerate()
+            .filter(move |&(index, _)| self.mask & 1 << index != 0)
+            .map(|(_, &offset)| offset)
+    }
+}
+impl Default for Neighborhood {
+    /// Returns the Moore neighborhood.
+    #[inline(always)]
+    fn default() -> Self {
+        Self::MOORE
+    }
+}
+#[cfg(feature = "serialization")]
+impl Serialize for Neighborhood {
+    /// Serializes the neighborhood as a sequence of offsets.
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        // The length is passed explicitly, since the iterator over the offsets doesn't know it.
+        let mut seq = s.serialize_seq(Some(self.len()))?;
+        for offset in self.offsets() {
+            seq.serialize_element(&offset)?;
+        }
+        seq.end()
+    }
+}
+#[cfg(feature = "serialization")]
+impl<'de> Deserialize<'de> for Neighborhood {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let offsets = Vec::<[isize; 2]>::deserialize(d)?;
+        Self::from_offsets(&offsets).map_err(serde::de::Error::custom)
+    }
+}
+
+/// The error type for [creating a neighborhood from offsets][m_from_offsets].
+///
+/// [m_from_offsets]: struct.Neighborhood.html#method.from_offsets "from_offsets — creates a neighborhood with the specified offsets from the center tile"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NeighborhoodError {
+    /// The offset `[0, 0]` was specified, which would make a tile its own neighbor.
+    CenterIncluded,
+    /// The specified offset is more than 3 tiles away from the center along one of the axes.
+    OffsetTooFar([isize; 2]),
+}
+impl fmt::Display for NeighborhoodError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CenterIncluded => write!(f,
+                "a tile can't be its own neighbor"
+            ),
+            Self::OffsetTooFar(offset) => write!(f,
+                "the offset {offset:?} is more than {MAX_RADIUS} tiles away from the center"
+            ),
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for NeighborhoodError {}
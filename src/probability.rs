@@ -0,0 +1,365 @@
+//! Exact mine probabilities for the closed tiles of a field, as seen by the player.
+//!
+//! Just like the [solver][solver], the probability engine only uses the information available to the player: the open numbers, the flags (which are trusted to be correct) and the total amount of mines on the field. **The hidden mine positions are never read.**
+//!
+//! The closed tiles touching at least one open number form the *frontier*, which is split into independent components: two frontier tiles belong to the same component if there's a chain of numbers connecting them. Every mine configuration which satisfies all numbers of a component is enumerated, and the configurations of all components are then combined and weighed by the amount of ways the remaining mines can be distributed over the closed tiles which don't touch any numbers (the *interior*). Since the amount of configurations of a component grows exponentially with its size, very long frontiers with little information in them are expensive to process.
+//!
+//! # Usage
+//! ```
+//! # use sweeper::{Field, TileState, probability};
+//! # use core::num::{NonZeroUsize, NonZeroU8};
+//! #
+//! // One mine around a number in the middle of a row of five:
+//! //   . . 1 . .
+//! let mut field = Field::<(), ()>::empty([NonZeroUsize::new(5).unwrap(), NonZeroUsize::new(1).unwrap()]);
+//! field[[2, 0]].state = TileState::OpenNumber(NonZeroU8::new(1).unwrap());
+//!
+//! let probabilities = probability::mine_probabilities(&field, 2).unwrap();
+//! let approx_eq = |a: f64, b: f64| (a - b).abs() < 1e-9;
+//! // The number is open, so it doesn't have a probability.
+//! assert_eq!(probabilities[[2, 0]], None);
+//! // One of the two tiles next to the number is a mine, and so is one of the two tiles on the edges.
+//! assert!(approx_eq(probabilities[[1, 0]].unwrap(), 0.5));
+//! assert!(approx_eq(probabilities[[0, 0]].unwrap(), 0.5));
+//! ```
+//!
+//! [solver]: ../solver/index.html "solver — a deterministic solver which only uses the information available to the player"
+
+use core::ops::Index;
+use alloc::{
+    vec::Vec,
+    collections::{BTreeMap, BTreeSet},
+};
+use crate::{
+    Field, FieldDimensions, FieldCoordinates,
+    solver::{Board, Cell, Constraint},
+};
+
+/// Calculates the probability of every closed tile on the specified field to contain a mine.
+///
+/// The calculation takes the visible numbers, the flags and the total amount of mines on the field into account. Returns `None` if the visible information is contradictory, i.e. if a number has more flags around it than its value, there are more flags than `total_mines` or no placement of the remaining mines matches all numbers. Flagged tiles are reported to contain a mine with a probability of 1, since the flags are trusted. See the [module-level documentation][module] for the details on how the probabilities are calculated.
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Field, probability::mine_probabilities};
+/// #
+/// // The one sees two flags, so they can't both be mines.
+/// let field: Field<(), ()> = "1F\nF.".parse().unwrap();
+/// assert!(mine_probabilities(&field, 2).is_none());
+/// // Two flags can't be placed on a field with one mine either.
+/// let field: Field<(), ()> = "1F\n.F".parse().unwrap();
+/// assert!(mine_probabilities(&field, 1).is_none());
+///
+/// let field: Field<(), ()> = "1F\n..".parse().unwrap();
+/// let probabilities = mine_probabilities(&field, 1).unwrap();
+/// assert_eq!(probabilities[[1, 1]], Some(0.0));
+/// ```
+///
+/// [module]: index.html "probability — exact mine probabilities for the closed tiles of a field"
+#[must_use = "calculating the probabilities involves enumerating mine configurations, which can be very expensive"]
+pub fn mine_probabilities<Ct, Cf>(field: &Field<Ct, Cf>, total_mines: usize) -> Option<MineProbabilities> {
+    let board = Board::from_field(field);
+    let cells = board.cells();
+    let flagged = cells.iter().filter(|&&c| c == Cell::Mine).count();
+    let remaining = total_mines.checked_sub(flagged)?;
+
+    let constraints = board.constraints()?;
+    let frontier = constraints.iter()
+        .flat_map(|constraint| constraint.tiles.iter().copied())
+        .collect::<BTreeSet<_>>();
+    let interior = cells.iter()
+        .enumerate()
+        .filter(|&(i, &c)| c == Cell::Closed && !frontier.contains(&i))
+        .count();
+
+    let components = components(&constraints)
+        .into_iter()
+        .map(Component::enumerate)
+        .collect::<Option<Vec<_>>>()?;
+
+    // Distribution of the total amount of mines on the frontier, and the same distribution without each of the components.
+    let total = components.iter()
+        .fold(alloc::vec![1.0], |acc, c| convolve(&acc, &c.counts));
+    let without = (0..components.len())
+        .map(|skip| {
+            components.iter()
+                .enumerate()
+                .filter(|&(i, _)| i != skip)
+                .fold(alloc::vec![1.0], |acc, (_, c)| convolve(&acc, &c.counts))
+        })
+        .collect::<Vec<_>>();
+    let weights = interior_weights(interior, remaining, total.len() - 1)?;
+    let normalizer = total.iter().zip(&weights).map(|(c, w)| c * w).sum::<f64>();
+    if normalizer <= 0.0 {
+        return None;
+    }
+
+    let mut probabilities = cells.iter()
+        .map(|&c| match c {
+            Cell::Open(_) | Cell::Void => None,
+            Cell::Mine => Some(1.0),
+            Cell::Closed => Some(0.0),
+        })
+        .collect::<Vec<_>>();
+    for (component, rest) in components.iter().zip(&without) {
+        for (&tile, per_count) in component.tiles.iter().zip(&component.mine_counts) {
+            let mut probability = 0.0;
+            for (k, count) in per_count.iter().enumerate() {
+                for (k_rest, rest_count) in rest.iter().enumerate() {
+                    probability += count * rest_count * weights[k + k_rest];
+                }
+            }
+            probabilities[tile] = Some(probability / normalizer);
+        }
+    }
+    if interior > 0 {
+        #[allow(clippy::cast_precision_loss)]
+        let interior_probability = total.iter()
+            .zip(&weights)
+            .enumerate()
+            .filter(|&(k, _)| k <= remaining)
+            .map(|(k, (c, w))| c * w * (remaining - k) as f64 / interior as f64)
+            .sum::<f64>() / normalizer;
+        for (i, cell) in cells.iter().enumerate() {
+            if *cell == Cell::Closed && !frontier.contains(&i) {
+                probabilities[i] = Some(interior_probability);
+            }
+        }
+    }
+    Some(MineProbabilities {dimensions: field.dimensions(), probabilities})
+}
+
+/// The probabilities of the tiles of a field to contain a mine, as calculated by [`mine_probabilities`][m_mp].
+///
+/// Indexing returns `None` for open tiles and the probability for closed ones.
+///
+/// [m_mp]: fn.mine_probabilities.html "mine_probabilities — calculates the probability of every closed tile on the specified field to contain a mine"
+#[derive(Clone, Debug, PartialEq)]
+pub struct MineProbabilities {
+    dimensions: FieldDimensions,
+    probabilities: Vec<Option<f64>>,
+}
+impl MineProbabilities {
+    /// Returns the dimensions of the field for which the probabilities were calculated.
+    #[inline(always)]
+    pub const fn dimensions(&self) -> FieldDimensions {
+        self.dimensions
+    }
+    /// Returns the probability of the specified tile to contain a mine, or `None` if the tile is open or the coordinates are out of bounds.
+    #[inline]
+    pub fn get(&self, coordinates: FieldCoordinates) -> Option<f64> {
+        let (width, height) = (self.dimensions[0].get(), self.dimensions[1].get());
+        if coordinates[0] >= width || coordinates[1] >= height {return None}
+        self.probabilities[coordinates[0] + coordinates[1] * width]
+    }
+    /// Returns an iterator over the closed tiles and their probabilities to contain a mine, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (FieldCoordinates, f64)> + '_ {
+        let width = self.dimensions[0].get();
+        self.probabilities.iter()
+            .enumerate()
+            .filter_map(move |(i, p)| p.map(|p| ([i % width, i / width], p)))
+    }
+    /// Returns the closed tile with the lowest probability to contain a mine and that probability, or `None` if there are no closed tiles. Ties are resolved in favor of the first such tile in row-major order.
+    ///
+    /// This is the tile a hint system would typically suggest opening when no tile is proven to be safe.
+    pub fn safest(&self) -> Option<(FieldCoordinates, f64)> {
+        self.iter().fold(None, |best, (coords, p)| match best {
+            Some((_, best_p)) if best_p <= p => best,
+            _ => Some((coords, p)),
+        })
+    }
+}
+impl Index<FieldCoordinates> for MineProbabilities {
+    type Output = Option<f64>;
+    /// Returns the probability of the tile at the column `index[0]` and row `index[1]` to contain a mine, or `None` if the tile is open.
+    ///
+    /// # Panics
+    /// Index checking is enabled for this method. For a version which returns `None` instead of panicking if the index is out of bounds, see `get`.
+    #[inline]
+    fn index(&self, coordinates: FieldCoordinates) -> &Self::Output {
+        let (width, height) = (self.dimensions[0].get(), self.dimensions[1].get());
+        assert!(coordinates[0] < width && coordinates[1] < height, "index out of bounds");
+        &self.probabilities[coordinates[0] + coordinates[1] * width]
+    }
+}
+
+/// An independent part of the frontier, along with the constraints which only involve its tiles. Constraints refer to the tiles by their position in `tiles`.
+struct Component {
+    tiles: Vec<usize>,
+    constraints: Vec<(Vec<usize>, usize)>,
+}
+/// The result of enumerating the mine configurations of a component.
+struct Enumerated {
+    /// The row-major indices of the tiles of the component.
+    tiles: Vec<usize>,
+    /// The amount of configurations with the specified amount of mines, relative to the total amount of configurations.
+    counts: Vec<f64>,
+    /// For every tile, the amount of configurations with the specified amount of mines in which the tile contains a mine, on the same scale as `counts`.
+    mine_counts: Vec<Vec<f64>>,
+}
+
+/// Splits the frontier into independent components.
+fn components(constraints: &[Constraint]) -> Vec<Component> {
+    // Union-find over the constraints, joined by the tiles they share.
+    let mut parent = (0..constraints.len()).collect::<Vec<_>>();
+    let mut owner = BTreeMap::<usize, usize>::new();
+    for (i, constraint) in constraints.iter().enumerate() {
+        for &tile in &constraint.tiles {
+            if let Some(&other) = owner.get(&tile) {
+                let (a, b) = (root(&mut parent, i), root(&mut parent, other));
+                parent[a] = b;
+            } else {
+                owner.insert(tile, i);
+            }
+        }
+    }
+    let mut by_root = BTreeMap::<usize, Vec<usize>>::new();
+    for i in 0..constraints.len() {
+        let r = root(&mut parent, i);
+        by_root.entry(r).or_default().push(i);
+    }
+    by_root.into_values()
+        .map(|members| {
+            // Order the tiles by the first constraint which mentions them, which keeps the tiles of each constraint close together and makes the enumeration prune early.
+            let mut tiles = Vec::<usize>::new();
+            for &c in &members {
+                for &tile in &constraints[c].tiles {
+                    if !tiles.contains(&tile) {
+                        tiles.push(tile);
+                    }
+                }
+            }
+            let constraints = members.iter()
+                .map(|&c| {
+                    let local = constraints[c].tiles.iter()
+                        .map(|tile| tiles.iter().position(|t| t == tile).unwrap_or_else(|| unreachable!("the tile was collected above")))
+                        .collect();
+                    (local, constraints[c].mines)
+                })
+                .collect();
+            Component {tiles, constraints}
+        })
+        .collect()
+}
+
+/// Finds the representative of the union-find set containing `i`, compressing the path along the way.
+fn root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+impl Component {
+    /// Enumerates all mine configurations which satisfy the constraints, or returns `None` if there are none.
+    fn enumerate(self) -> Option<Enumerated> {
+        let n = self.tiles.len();
+        let mut touching = alloc::vec![Vec::new(); n];
+        for (c, (tiles, _)) in self.constraints.iter().enumerate() {
+            for &tile in tiles {
+                touching[tile].push(c);
+            }
+        }
+        let mut state = Enumeration {
+            component: &self,
+            touching,
+            assigned_mines: alloc::vec![0; self.constraints.len()],
+            unassigned: self.constraints.iter().map(|(tiles, _)| tiles.len()).collect(),
+            assignment: alloc::vec![false; n],
+            counts: alloc::vec![0.0; n + 1],
+            mine_counts: alloc::vec![alloc::vec![0.0; n + 1]; n],
+        };
+        state.search(0, 0);
+
+        let sum = state.counts.iter().sum::<f64>();
+        if sum <= 0.0 {
+            return None;
+        }
+        // Normalize to keep the numbers in range when combining many components.
+        let Enumeration {mut counts, mut mine_counts, ..} = state;
+        for c in counts.iter_mut().chain(mine_counts.iter_mut().flatten()) {
+            *c /= sum;
+        }
+        Some(Enumerated {tiles: self.tiles, counts, mine_counts})
+    }
+}
+/// The state of the backtracking enumeration of a component.
+struct Enumeration<'c> {
+    component: &'c Component,
+    /// For every tile, the constraints which involve it.
+    touching: Vec<Vec<usize>>,
+    /// For every constraint, the amount of mines among its assigned tiles.
+    assigned_mines: Vec<usize>,
+    /// For every constraint, the amount of its tiles which are not assigned yet.
+    unassigned: Vec<usize>,
+    assignment: Vec<bool>,
+    counts: Vec<f64>,
+    mine_counts: Vec<Vec<f64>>,
+}
+impl Enumeration<'_> {
+    fn search(&mut self, tile: usize, mines: usize) {
+        if tile == self.assignment.len() {
+            self.counts[mines] += 1.0;
+            for (t, &is_mine) in self.assignment.iter().enumerate() {
+                if is_mine {
+                    self.mine_counts[t][mines] += 1.0;
+                }
+            }
+            return;
+        }
+        for &is_mine in &[false, true] {
+            let mut feasible = true;
+            for &c in &self.touching[tile] {
+                self.unassigned[c] -= 1;
+                if is_mine {self.assigned_mines[c] += 1}
+                let required = self.component.constraints[c].1;
+                if self.assigned_mines[c] > required || self.assigned_mines[c] + self.unassigned[c] < required {
+                    feasible = false;
+                }
+            }
+            if feasible {
+                self.assignment[tile] = is_mine;
+                self.search(tile + 1, mines + usize::from(is_mine));
+                self.assignment[tile] = false;
+            }
+            for &c in &self.touching[tile] {
+                self.unassigned[c] += 1;
+                if is_mine {self.assigned_mines[c] -= 1}
+            }
+        }
+    }
+}
+
+/// Convolves two distributions over the amount of mines.
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut result = alloc::vec![0.0; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            result[i + j] += x * y;
+        }
+    }
+    result
+}
+/// Returns, for every amount of mines on the frontier from zero to `max_frontier`, a number proportional to the amount of ways to place the remaining mines in the interior, or `None` if no amount works.
+///
+/// The binomial coefficients are calculated through their ratios and rescaled on the go, since they quickly get too big even for `f64` on large fields.
+#[allow(clippy::cast_precision_loss)]
+fn interior_weights(interior: usize, remaining: usize, max_frontier: usize) -> Option<Vec<f64>> {
+    let mut weights = alloc::vec![0.0; max_frontier + 1];
+    let lowest = remaining.saturating_sub(interior);
+    let highest = remaining.min(max_frontier);
+    if lowest > highest {
+        return None;
+    }
+    // C(interior, remaining - highest) is the reference point. Every step down in the amount of frontier mines puts one more mine into the interior: C(u, r) = C(u, r - 1) * (u - r + 1) / r.
+    weights[highest] = 1.0;
+    for k in (lowest..highest).rev() {
+        let r = remaining - k;
+        weights[k] = weights[k + 1] * (interior - r + 1) as f64 / r as f64;
+        if weights[k] > 1e100 {
+            weights[k..=highest].iter_mut().for_each(|w| *w /= 1e100);
+        }
+    }
+    Some(weights)
+}
@@ -0,0 +1,272 @@
+//! Recording and playback of games.
+//!
+//! Replays are stored in the crate's own format, which can be saved with Serde. Importing and exporting the replay files of other Minesweeper clones, such as the `.avf` files of Minesweeper Arbiter and the `.rmv` files of Viennasweeper, is not supported.
+
+use core::{
+    fmt::{self, Formatter},
+    time::Duration,
+};
+use alloc::vec::Vec;
+#[cfg(feature = "serialization")]
+use serde::{Serialize, Deserialize};
+use crate::{
+    Field, FieldDimensions, FieldCoordinates, Topology, Neighborhood,
+    ChordOutcome, RecursiveChordOutcome,
+    TileState, Flag, ClickOutcome,
+    Game, GamePhase, ManualClock, Action,
+};
+
+/// A recording of a game: the initial layout of the mines and every action of the player, together with its time and outcome.
+///
+/// Replays are recorded by [`Game`][game] automatically, as long as the game was started on a field without open tiles and flags. They can be played back step by step by the frontend, or [verified][m_verify] by running all actions again on the original field and checking that every outcome is reproduced exactly, which can be used to validate results submitted to a leaderboard. With the `serialization` feature, replays can be serialized with Serde.
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Field, Game, GamePhase, ManualClock, TileState, Flag};
+/// # use core::{num::NonZeroUsize, time::Duration};
+/// #
+/// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(4).unwrap(), NonZeroUsize::new(1).unwrap()]);
+/// field[[0, 0]].state = TileState::Mine(Flag::NotFlagged);
+///
+/// let clock = ManualClock::new();
+/// let mut game = Game::new(field, &clock);
+/// clock.advance(Duration::from_millis(700));
+/// game.open([3, 0]);
+/// assert_eq!(game.phase(), GamePhase::Won);
+///
+/// let replay = game.replay().unwrap().clone();
+/// assert_eq!(replay.mines, vec![[0, 0]]);
+/// assert_eq!(replay.events[0].time, Duration::from_millis(700));
+/// // Running the replay again gives the same result.
+/// let replayed = replay.verify::<()>().unwrap();
+/// assert_eq!(replayed.phase(), GamePhase::Won);
+/// ```
+///
+/// [game]: struct.Game.html "Game — a Minesweeper game session"
+/// [m_verify]: #method.verify "verify — runs the replay on the field it was recorded on and checks that it reproduces the same outcomes"
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct Replay<Cf> {
+    /// The dimensions of the field.
+    pub dimensions: FieldDimensions,
+    /// The topology of the field. Replays recorded before topologies were introduced are loaded as bounded.
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub topology: Topology,
+    /// The neighborhood of the field. Replays recorded before neighborhoods were introduced are loaded with the Moore neighborhood.
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub neighborhood: Neighborhood,
+    /// The locations of the mines before the first click, in row-major order.
+    pub mines: Vec<FieldCoordinates>,
+    /// The locations of the void tiles, in row-major order. Replays recorded before void tiles were introduced are loaded without any.
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub voids: Vec<FieldCoordinates>,
+    /// The actions of the player, in the order they were performed.
+    pub events: Vec<ReplayEvent<Cf>>,
+}
+impl<Cf> Replay<Cf> {
+    /// Creates a replay without any events for the specified field, or returns `None` if the field has open tiles or flags, since replays can only start from an untouched field.
+    #[must_use = "traversing the entire field is obscenely expensive"]
+    pub fn new<Ct>(field: &Field<Ct, Cf>) -> Option<Self> {
+        let (mut mines, mut voids) = (Vec::new(), Vec::new());
+        for (location, tile) in field.enumerate_tiles() {
+            match tile.state {
+                TileState::Mine(Flag::NotFlagged) => mines.push(location),
+                TileState::Void => voids.push(location),
+                TileState::ClosedEmpty(Flag::NotFlagged) => {},
+                _ => return None,
+            }
+        }
+        Some(Self {dimensions: field.dimensions(), topology: field.topology(), neighborhood: field.neighborhood(), mines, voids, events: Vec::new()})
+    }
+    /// Builds the field the replay was recorded on, as it was before the first click.
+    ///
+    /// # Errors
+    /// Returns [`MineOutOfBounds`][e_moob] if any of the mines is outside the field and [`VoidOutOfBounds`][e_voob] if any of the void tiles is.
+    ///
+    /// [e_moob]: enum.ReplayError.html#variant.MineOutOfBounds "ReplayError::MineOutOfBounds — one of the mines of the replay is outside the field"
+    /// [e_voob]: enum.ReplayError.html#variant.VoidOutOfBounds "ReplayError::VoidOutOfBounds — one of the void tiles of the replay is outside the field"
+    pub fn field<Ct: Default>(&self) -> Result<Field<Ct, Cf>, ReplayError> {
+        let mut field = Field::empty(self.dimensions)
+            .with_topology(self.topology)
+            .with_neighborhood(self.neighborhood);
+        for &mine in &self.mines {
+            if mine[0] >= self.dimensions[0].get() || mine[1] >= self.dimensions[1].get() {
+                return Err(ReplayError::MineOutOfBounds(mine));
+            }
+            field[mine].state = TileState::Mine(Flag::NotFlagged);
+        }
+        for &void in &self.voids {
+            if void[0] >= self.dimensions[0].get() || void[1] >= self.dimensions[1].get() {
+                return Err(ReplayError::VoidOutOfBounds(void));
+            }
+            field[void].state = TileState::Void;
+        }
+        Ok(field)
+    }
+}
+impl<Cf: Clone + PartialEq> Replay<Cf> {
+    /// Runs the replay on the field it was recorded on and checks that every action has the recorded outcome, returning the game in the state after the last action.
+    ///
+    /// The returned game uses a [`ManualClock`][manual] set to the time of the last action.
+    ///
+    /// # Errors
+    /// Returns [`MineOutOfBounds`][e_moob] if any of the mines is outside the field, [`VoidOutOfBounds`][e_voob] if any of the void tiles is, [`TimeWentBackwards`][e_twb] if the events are not ordered by time and [`Diverged`][e_d] if an action has a different outcome than the recorded one.
+    ///
+    /// [manual]: struct.ManualClock.html "ManualClock — a clock which only advances when explicitly told to"
+    /// [e_moob]: enum.ReplayError.html#variant.MineOutOfBounds "ReplayError::MineOutOfBounds — one of the mines of the replay is outside the field"
+    /// [e_voob]: enum.ReplayError.html#variant.VoidOutOfBounds "ReplayError::VoidOutOfBounds — one of the void tiles of the replay is outside the field"
+    /// [e_twb]: enum.ReplayError.html#variant.TimeWentBackwards "ReplayError::TimeWentBackwards — an event happened earlier than the one before it"
+    /// [e_d]: enum.ReplayError.html#variant.Diverged "ReplayError::Diverged — an action had a different outcome than the recorded one"
+    pub fn verify<Ct: Default>(&self) -> Result<Game<Ct, Cf, ManualClock>, ReplayError> {
+        self.verify_on(self.field()?)
+    }
+    /// Runs the replay on the specified field and checks that every action has the recorded outcome, returning the game in the state after the last action.
+    ///
+    /// This is useful for running a replay submitted by a client on the field stored by the server, which makes sure that the replay was actually recorded on that field.
+    ///
+    /// # Errors
+    /// Returns [`LayoutMismatch`][e_lm] if the field has different dimensions, topology, neighborhood, mines or void tiles than the ones recorded in the replay or has open tiles or flags, [`TimeWentBackwards`][e_twb] if the events are not ordered by time and [`Diverged`][e_d] if an action has a different outcome than the recorded one.
+    ///
+    /// [e_lm]: enum.ReplayError.html#variant.LayoutMismatch "ReplayError::LayoutMismatch — the field doesn't match the one the replay was recorded on"
+    /// [e_twb]: enum.ReplayError.html#variant.TimeWentBackwards "ReplayError::TimeWentBackwards — an event happened earlier than the one before it"
+    /// [e_d]: enum.ReplayError.html#variant.Diverged "ReplayError::Diverged — an action had a different outcome than the recorded one"
+    pub fn verify_on<Ct>(&self, field: Field<Ct, Cf>) -> Result<Game<Ct, Cf, ManualClock>, ReplayError> {
+        match Self::new(&field) {
+            Some(layout) if layout.dimensions == self.dimensions
+                && layout.topology == self.topology
+                && layout.neighborhood == self.neighborhood
+                && layout.mines == self.mines
+                && layout.voids == self.voids => {},
+            _ => return Err(ReplayError::LayoutMismatch),
+        }
+        let mut game = Game::new(field, ManualClock::new());
+        let mut last_time = Duration::default();
+        for (index, event) in self.events.iter().enumerate() {
+            if event.time < last_time {
+                return Err(ReplayError::TimeWentBackwards {event: index});
+            }
+            last_time = event.time;
+            game.clock().set(event.time);
+            let reproduced = match &event.action {
+                ReplayAction::Open {location, outcome} => game.open(*location) == Some(*outcome),
+                ReplayAction::Chord {location, outcome} => game.chord(*location).as_ref() == Some(outcome),
+                ReplayAction::RecursiveChord {location, outcome} => game.recursive_chord(*location).as_ref() == Some(outcome),
+                ReplayAction::SetFlag {location, flag, previous} => game.set_flag(*location, flag.clone()).as_ref() == Some(previous),
+                ReplayAction::Undo(action) => game.undo() == Some(*action),
+                ReplayAction::Redo(action) => game.redo() == Some(*action),
+                ReplayAction::ClearJournal => {game.clear_journal(); true},
+            };
+            if !reproduced || game.phase() != event.phase {
+                return Err(ReplayError::Diverged {event: index});
+            }
+        }
+        Ok(game)
+    }
+}
+
+/// An action of the player recorded in a [replay][replay].
+///
+/// [replay]: struct.Replay.html "Replay — a recording of a game"
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct ReplayEvent<Cf> {
+    /// The time of the action, counted from the creation of the game.
+    pub time: Duration,
+    /// The action and its outcome.
+    pub action: ReplayAction<Cf>,
+    /// The phase of the game after the action.
+    pub phase: GamePhase,
+}
+
+/// An action recorded in a [replay][replay], together with its outcome.
+///
+/// Every variant corresponds to a method of [`Game`][game] with the same name, and the outcome is the return value of that method. Actions on tiles outside the field and undoing or redoing with an empty journal are not recorded.
+///
+/// [replay]: struct.Replay.html "Replay — a recording of a game"
+/// [game]: struct.Game.html "Game — a Minesweeper game session"
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub enum ReplayAction<Cf> {
+    /// A tile was opened.
+    Open {
+        /// The tile which was opened.
+        location: FieldCoordinates,
+        /// The outcome of opening the tile.
+        outcome: ClickOutcome,
+    },
+    /// A chord was performed.
+    Chord {
+        /// The tile the chord was performed on.
+        location: FieldCoordinates,
+        /// The outcome of the chord.
+        outcome: ChordOutcome,
+    },
+    /// A recursive chord was performed.
+    RecursiveChord {
+        /// The tile the recursive chord was started on.
+        location: FieldCoordinates,
+        /// The outcomes of the chords which opened anything.
+        outcome: Vec<RecursiveChordOutcome>,
+    },
+    /// A flag was set on a tile.
+    SetFlag {
+        /// The tile the flag was set on.
+        location: FieldCoordinates,
+        /// The flag which was set.
+        flag: Flag<Cf>,
+        /// The flag which was on the tile before.
+        previous: Flag<Cf>,
+    },
+    /// The specified action was undone.
+    Undo(Action),
+    /// The specified action was redone.
+    Redo(Action),
+    /// The journal was cleared.
+    ClearJournal,
+}
+
+/// The error type for [replay verification][m_verify].
+///
+/// [m_verify]: struct.Replay.html#method.verify "verify — runs the replay on the field it was recorded on and checks that it reproduces the same outcomes"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ReplayError {
+    /// One of the mines of the replay is outside the field.
+    MineOutOfBounds(FieldCoordinates),
+    /// One of the void tiles of the replay is outside the field.
+    VoidOutOfBounds(FieldCoordinates),
+    /// The field the replay was run on doesn't match the one the replay was recorded on.
+    LayoutMismatch,
+    /// The event with the specified index happened earlier than the one before it.
+    TimeWentBackwards {
+        /// The index of the event in the replay.
+        event: usize,
+    },
+    /// The action of the event with the specified index had a different outcome than the recorded one, or resulted in a different game phase.
+    Diverged {
+        /// The index of the event in the replay.
+        event: usize,
+    },
+}
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MineOutOfBounds(location) => write!(f,
+                "the mine at {location:?} is outside the field"
+            ),
+            Self::VoidOutOfBounds(location) => write!(f,
+                "the void tile at {location:?} is outside the field"
+            ),
+            Self::LayoutMismatch => write!(f,
+                "the field doesn't match the one the replay was recorded on"
+            ),
+            Self::TimeWentBackwards {event} => write!(f,
+                "event {event} happened earlier than the one before it"
+            ),
+            Self::Diverged {event} => write!(f,
+                "event {event} didn't reproduce the recorded outcome"
+            ),
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for ReplayError {}
@@ -0,0 +1,243 @@
+//! Short codes for sharing mine layouts.
+//!
+//! A share code stores the dimensions of a field and the locations of its mines and void tiles in a compact string made only of URL-safe characters (`A`–`Z`, `a`–`z`, `0`–`9`, `-` and `_`), which can be pasted into a chat or put into a link. The state of the tiles other than the mines, like flags and open tiles, is not stored — decoding a share code always gives a fresh field.
+//!
+//! Internally, a share code is the URL-safe Base64 encoding (without padding) of the following bytes: the version of the format, the width and the height as LEB128 variable-length integers, one bit per tile in row-major order (set if the tile contains a mine, starting from the least significant bit of each byte) and a little-endian CRC-32 checksum of all the previous bytes. A beginner field takes 24 characters, an expert one takes 90. The version is `1` for fields without void tiles; fields with them use version `2`, which has a second bitmap of the same layout with the void tiles right after the one with the mines.
+//!
+//! # Usage
+//! ```
+//! # use sweeper::{Field, TileState, Flag, ShareCodeError};
+//! # use core::num::NonZeroUsize;
+//! #
+//! let mut field = Field::<(), ()>::empty([NonZeroUsize::new(9).unwrap(); 2]);
+//! field[[3, 4]].state = TileState::Mine(Flag::Flagged);
+//! field[[8, 8]].state = TileState::Mine(Flag::NotFlagged);
+//!
+//! let code = field.share_code();
+//! let decoded = Field::<(), ()>::from_share_code(&code).unwrap();
+//! assert!(decoded[[3, 4]].state.is_mine());
+//! assert!(decoded[[8, 8]].state.is_mine());
+//! // Flags are not shared.
+//! assert!(!decoded[[3, 4]].state.is_flagged());
+//!
+//! // A code which was cut off while copying is rejected.
+//! assert!(Field::<(), ()>::from_share_code(&code[..code.len() - 3]).is_err());
+//! ```
+
+use core::{
+    convert::TryFrom,
+    fmt::{self, Formatter},
+    num::NonZeroUsize,
+};
+use alloc::{
+    string::String,
+    vec::Vec,
+};
+use crate::{
+    Field, TileState, Flag,
+    binary::crc32,
+};
+
+const VERSION: u8 = 2;
+/// The version written for fields without void tiles, which can be read by decoders predating them.
+const VERSION_WITHOUT_VOIDS: u8 = 1;
+const CHECKSUM_SIZE: usize = 4;
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+impl<Ct, Cf> Field<Ct, Cf> {
+    /// Encodes the dimensions, the mines and the void tiles of the field as a [share code][share].
+    ///
+    /// [share]: share/index.html "share — short codes for sharing mine layouts"
+    #[must_use = "this performs a memory allocation and traverses the entire field"]
+    pub fn share_code(&self) -> String {
+        let [width, height] = self.dimensions();
+        let has_voids = self.all_tiles().any(|tile| tile.state.is_void());
+        let mut bytes = alloc::vec![if has_voids {VERSION} else {VERSION_WITHOUT_VOIDS}];
+        write_leb128(&mut bytes, width.get());
+        write_leb128(&mut bytes, height.get());
+        let bitmap_start = bytes.len();
+        let bitmap_size = (width.get() * height.get() + 7) / 8;
+        bytes.resize(bitmap_start + if has_voids {2 * bitmap_size} else {bitmap_size}, 0);
+        for (index, tile) in self.all_tiles().enumerate() {
+            if tile.state.is_mine() {
+                bytes[bitmap_start + index / 8] |= 1 << (index % 8);
+            } else if tile.state.is_void() {
+                bytes[bitmap_start + bitmap_size + index / 8] |= 1 << (index % 8);
+            }
+        }
+        let checksum = crc32(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        encode_base64(&bytes)
+    }
+}
+impl<Ct: Default, Cf> Field<Ct, Cf> {
+    /// Decodes a [share code][share] into a field with the mines and the void tiles in the stored locations and all other tiles closed and unflagged.
+    ///
+    /// # Errors
+    /// Returns an error if the code is malformed, truncated or corrupt. See [`ShareCodeError`][sce] for the exact checks performed.
+    ///
+    /// [share]: share/index.html "share — short codes for sharing mine layouts"
+    /// [sce]: share/enum.ShareCodeError.html "ShareCodeError — the error type for decoding share codes"
+    pub fn from_share_code(code: &str) -> Result<Self, ShareCodeError> {
+        let bytes = decode_base64(code)?;
+        let (&version, mut rest) = bytes.split_first().ok_or(ShareCodeError::Truncated)?;
+        if version != VERSION && version != VERSION_WITHOUT_VOIDS {
+            return Err(ShareCodeError::UnsupportedVersion(version));
+        }
+        let width = read_leb128(&mut rest)?;
+        let height = read_leb128(&mut rest)?;
+        let dimensions = match (NonZeroUsize::new(width), NonZeroUsize::new(height)) {
+            (Some(width), Some(height)) => [width, height],
+            _ => return Err(ShareCodeError::InvalidDimensions),
+        };
+        let area = width.checked_mul(height).ok_or(ShareCodeError::InvalidDimensions)?;
+
+        // Check the length before allocating the field, so that a corrupt code can't request a huge allocation.
+        let bitmap_size = area / 8 + usize::from(area % 8 != 0);
+        let num_bitmaps = if version == VERSION {2} else {1};
+        let expected_size = bitmap_size.checked_mul(num_bitmaps)
+            .and_then(|size| size.checked_add(CHECKSUM_SIZE))
+            .ok_or(ShareCodeError::Truncated)?;
+        if rest.len() < expected_size {
+            return Err(ShareCodeError::Truncated);
+        }
+        if rest.len() > expected_size {
+            return Err(ShareCodeError::TrailingData);
+        }
+        let (data, checksum) = bytes.split_at(bytes.len() - CHECKSUM_SIZE);
+        if crc32(data).to_le_bytes() != checksum {
+            return Err(ShareCodeError::ChecksumMismatch);
+        }
+
+        let (mines, voids) = rest[..bitmap_size * num_bitmaps].split_at(bitmap_size);
+        let is_set = |bitmap: &[u8], index: usize| bitmap.get(index / 8).map_or(false, |byte| byte & (1 << (index % 8)) != 0);
+        let mut field = Self::empty(dimensions);
+        for index in 0..area {
+            if is_set(mines, index) {
+                field[[index % width, index / width]].state = TileState::Mine(Flag::NotFlagged);
+            } else if is_set(voids, index) {
+                field[[index % width, index / width]].state = TileState::Void;
+            }
+        }
+        Ok(field)
+    }
+}
+
+fn write_leb128(bytes: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let [byte, ..] = value.to_le_bytes();
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte & 0x7F);
+            break;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+fn read_leb128(bytes: &mut &[u8]) -> Result<usize, ShareCodeError> {
+    let mut value = 0_u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = bytes.split_first().ok_or(ShareCodeError::Truncated)?;
+        *bytes = rest;
+        let bits = u64::from(byte & 0x7F);
+        if bits << shift >> shift != bits {
+            return Err(ShareCodeError::InvalidDimensions);
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return usize::try_from(value).map_err(|_| ShareCodeError::InvalidDimensions);
+        }
+    }
+    Err(ShareCodeError::InvalidDimensions)
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity((bytes.len() * 4 + 2) / 3);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter()
+            .enumerate()
+            .fold(0_u32, |group, (i, &byte)| group | u32::from(byte) << (16 - 8 * i));
+        // Every byte takes 8 bits, which need one sextet plus one more for the remainder.
+        for i in 0..=chunk.len() {
+            encoded.push(char::from(ALPHABET[(group >> (18 - 6 * i) & 0x3F) as usize]));
+        }
+    }
+    encoded
+}
+fn decode_base64(code: &str) -> Result<Vec<u8>, ShareCodeError> {
+    let mut bytes = Vec::with_capacity(code.len() * 3 / 4);
+    let mut sextets = [0_u8; 4];
+    for (chunk_index, chunk) in code.as_bytes().chunks(4).enumerate() {
+        for (i, &c) in chunk.iter().enumerate() {
+            let position = chunk_index * 4 + i;
+            sextets[i] = ALPHABET.iter()
+                .position(|&a| a == c)
+                .and_then(|sextet| u8::try_from(sextet).ok())
+                .ok_or_else(|| ShareCodeError::InvalidCharacter {
+                    position,
+                    // The position is a character boundary, since all characters before it are ASCII.
+                    character: code[position..].chars().next().unwrap_or_default(),
+                })?;
+        }
+        // A single sextet doesn't make up a whole byte.
+        if chunk.len() == 1 {
+            return Err(ShareCodeError::Truncated);
+        }
+        let group = sextets[..chunk.len()].iter()
+            .enumerate()
+            .fold(0_u32, |group, (i, &sextet)| group | u32::from(sextet) << (18 - 6 * i));
+        let [_, high, middle, low] = group.to_be_bytes();
+        bytes.extend_from_slice(&[high, middle, low][..chunk.len() - 1]);
+    }
+    Ok(bytes)
+}
+
+/// The error type for decoding [share codes][share].
+///
+/// [share]: index.html "share — short codes for sharing mine layouts"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ShareCodeError {
+    /// The character at the specified byte position is not allowed in share codes.
+    InvalidCharacter {
+        /// The byte position of the character in the code.
+        position: usize,
+        /// The character itself.
+        character: char,
+    },
+    /// The code ends too early, which usually means that it wasn't copied completely.
+    Truncated,
+    /// There is more data after the end of the code.
+    TrailingData,
+    /// The code was created by a newer version of the format, which is not supported.
+    UnsupportedVersion(u8),
+    /// The width or height is zero or too big for the platform.
+    InvalidDimensions,
+    /// The checksum doesn't match the data, which means that the code was mistyped or altered.
+    ChecksumMismatch,
+}
+impl fmt::Display for ShareCodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter {position, character} => write!(f,
+                "the character {character:?} at position {position} is not allowed in share codes"
+            ),
+            Self::Truncated => write!(f,
+                "the share code is incomplete"
+            ),
+            Self::TrailingData => write!(f,
+                "the share code has extra data at the end"
+            ),
+            Self::UnsupportedVersion(version) => write!(f,
+                "version {version} of share codes is not supported"
+            ),
+            Self::InvalidDimensions => write!(f,
+                "the dimensions of the field are zero or too big"
+            ),
+            Self::ChecksumMismatch => write!(f,
+                "the share code is corrupt or was mistyped"
+            ),
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for ShareCodeError {}
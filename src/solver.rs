@@ -0,0 +1,285 @@
+//! A deterministic solver which only uses the information available to the player.
+//!
+//! The solver looks at a field the same way a player does: open tiles tell it the amount of mines around them, flags tell it where the player believes the mines are, and everything else is unknown. **The hidden mine positions are never read**, which makes it suitable for hints and auto-play features without spoiling anything the player couldn't have figured out on their own.
+//!
+//! Every [deduction][deduction] is reported along with the [rule][rule] which produced it and the number tiles it was derived from, so that frontends can explain the reasoning to the player.
+//!
+//! # Usage
+//! ```
+//! # use sweeper::{Field, TileState, Flag, solver::{self, Rule, Verdict}};
+//! # use core::num::{NonZeroUsize, NonZeroU8};
+//! #
+//! // The classic 1-2-1 pattern:
+//! //   1 2 1
+//! //   . . .
+//! let mut field = Field::<(), ()>::empty([NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(2).unwrap()]);
+//! let number = |n| TileState::OpenNumber(NonZeroU8::new(n).unwrap());
+//! field[[0, 0]].state = number(1);
+//! field[[1, 0]].state = number(2);
+//! field[[2, 0]].state = number(1);
+//!
+//! let deductions = solver::deduce(&field, None);
+//! // The tiles under the ones are mines, which is deduced by comparing them to the two.
+//! assert_eq!(deductions.len(), 2);
+//! assert!(deductions.iter().all(|d| d.verdict == Verdict::Mine && d.rule == Rule::Pair));
+//! assert_eq!(deductions[0].location, [0, 1]);
+//! assert_eq!(deductions[1].location, [2, 1]);
+//!
+//! // After the player flags them, the tile under the two is proven safe.
+//! for deduction in &deductions {
+//!     field[deduction.location].state = TileState::ClosedEmpty(Flag::Flagged);
+//! }
+//! let deductions = solver::deduce(&field, None);
+//! assert_eq!(deductions[0].location, [1, 1]);
+//! assert_eq!(deductions[0].verdict, Verdict::Safe);
+//! assert_eq!(deductions[0].rule, Rule::SingleTile);
+//! ```
+//!
+//! [deduction]: struct.Deduction.html "Deduction — a fact about a closed tile which was proven by the solver"
+//! [rule]: enum.Rule.html "Rule — a reasoning rule used by the solver"
+
+use alloc::{
+    vec::Vec,
+    collections::BTreeMap,
+};
+#[cfg(feature = "serialization")]
+use serde::{Serialize, Deserialize};
+use crate::{
+    Field, FieldDimensions, FieldCoordinates,
+    TileState, Topology, Neighborhood, NeighborLocations,
+};
+
+/// Returns all tiles which can be proven to be safe or to contain a mine on the specified field, as seen by the player.
+///
+/// Open tiles provide the amount of mines around them, while closed tiles with a `Flagged` flag are **trusted to be mines**: if the player placed a flag incorrectly, the deductions based on it will be incorrect too. Closed tiles with custom flags are treated like closed tiles without a flag. If the total amount of mines on the field is known to the player (which it usually is), passing it allows the solver to use the [global mine count rule][r_gmc].
+///
+/// If the visible information is contradictory, for example if a number has more flags around it than its value or there are more flags than `total_mines`, nothing can be proven and no deductions are returned.
+///
+/// Every closed tile is reported at most once, along with the simplest rule which proves it. The deductions are sorted by rule, from the simplest to the most complex one, and then by location in row-major order. Since opening safe tiles and flagging mines typically uncovers new information, a frontend which wants to solve the field step by step should apply the deductions and call this function again.
+///
+/// [r_gmc]: enum.Rule.html#variant.GlobalMineCount "GlobalMineCount — the total amount of mines on the field limits the remaining closed tiles"
+#[must_use = "running the solver involves traversing the entire field and memory allocation"]
+pub fn deduce<Ct, Cf>(field: &Field<Ct, Cf>, total_mines: Option<usize>) -> Vec<Deduction> {
+    let width = field.dimensions()[0].get();
+    let board = Board::from_field(field);
+    let mut deductions = board.deduce(total_mines)
+        .into_iter()
+        .map(|(index, is_mine, rule, sources)| Deduction {
+            location: [index % width, index / width],
+            verdict: if is_mine {Verdict::Mine} else {Verdict::Safe},
+            rule,
+            sources: sources.into_iter().map(|i| [i % width, i / width]).collect(),
+        })
+        .collect::<Vec<_>>();
+    deductions.sort_by_key(|d| (d.rule, d.location[1], d.location[0]));
+    deductions
+}
+
+/// A fact about a closed tile which was proven by the solver.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct Deduction {
+    /// The location of the tile which the deduction is about.
+    pub location: FieldCoordinates,
+    /// Whether the tile is safe or contains a mine.
+    pub verdict: Verdict,
+    /// The rule which was used to prove the verdict.
+    pub rule: Rule,
+    /// The locations of the number tiles which the deduction was derived from. Empty for the [global mine count rule][r_gmc].
+    ///
+    /// [r_gmc]: enum.Rule.html#variant.GlobalMineCount "GlobalMineCount — the total amount of mines on the field limits the remaining closed tiles"
+    pub sources: Vec<FieldCoordinates>,
+}
+/// The verdict of a [deduction][deduction].
+///
+/// [deduction]: struct.Deduction.html "Deduction — a fact about a closed tile which was proven by the solver"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub enum Verdict {
+    /// The tile doesn't contain a mine and can be opened.
+    Safe,
+    /// The tile contains a mine and can be flagged.
+    Mine,
+}
+impl Verdict {
+    /// Returns `true` for the `Mine` verdict, `false` otherwise.
+    #[inline(always)]
+    pub fn is_mine(self) -> bool {
+        self == Self::Mine
+    }
+}
+/// A reasoning rule used by the solver.
+///
+/// The variants are declared in the order of increasing complexity, which is also the order used by the `Ord` implementation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub enum Rule {
+    /// A single number tile proves the verdict on its own: either it already has as many flagged neighbors as its number, making its other closed neighbors safe, or it has exactly as many closed neighbors as its number is missing, making them all mines.
+    SingleTile,
+    /// Two number tiles with overlapping closed neighbors prove the verdict together: if the difference between the amounts of mines they're missing equals the amount of closed tiles only the first one touches, those tiles are all mines and the ones only the second one touches are all safe. This includes the case of the closed neighbors of one number being a subset of the ones of the other.
+    Pair,
+    /// The total amount of mines on the field proves the verdict: either all mines are already flagged, making every other closed tile safe, or the amount of closed tiles equals the amount of mines which aren't flagged, making them all mines.
+    GlobalMineCount,
+}
+
+/// The state of a tile as known to the player.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Cell {
+    /// The tile is closed and nothing is known about it.
+    Closed,
+    /// The tile is closed and is known to contain a mine.
+    Mine,
+    /// The tile is open and has the specified amount of neighboring mines.
+    Open(u8),
+    /// The tile is a hole in the field, which neither holds a mine nor shows a number.
+    Void,
+}
+
+/// A constraint imposed by an open number: the closed tiles around it contain exactly the specified amount of mines.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Constraint {
+    /// The closed tiles around the number, sorted.
+    pub(crate) tiles: Vec<usize>,
+    /// The amount of mines among them.
+    pub(crate) mines: usize,
+    /// The location of the number.
+    source: usize,
+}
+
+/// A deduction produced by the solver, in terms of row-major indices: the tile, whether it's a mine, the rule and the number tiles it was derived from.
+pub(crate) type RawDeduction = (usize, bool, Rule, Vec<usize>);
+
+/// The player's knowledge about a field, stored in row-major order.
+#[derive(Clone, Debug)]
+pub(crate) struct Board {
+    dimensions: FieldDimensions,
+    topology: Topology,
+    neighborhood: Neighborhood,
+    cells: Vec<Cell>,
+}
+impl Board {
+    /// Creates a board on which nothing is known yet.
+    pub(crate) fn new(dimensions: FieldDimensions, topology: Topology, neighborhood: Neighborhood) -> Self {
+        let area = dimensions[0].get() * dimensions[1].get();
+        Self {dimensions, topology, neighborhood, cells: alloc::vec![Cell::Closed; area]}
+    }
+    /// Creates a board with the knowledge the player has about the specified field: open tiles provide their numbers and flagged tiles are assumed to be mines.
+    pub(crate) fn from_field<Ct, Cf>(field: &Field<Ct, Cf>) -> Self {
+        let mut board = Self::new(field.dimensions(), field.topology(), field.neighborhood());
+        for (cell, tile) in board.cells.iter_mut().zip(field.all_tiles()) {
+            *cell = match &tile.state {
+                TileState::OpenEmpty => Cell::Open(0),
+                TileState::OpenNumber(number) => Cell::Open(number.get()),
+                TileState::Void => Cell::Void,
+                // Only look at the flag, since whether the tile is a mine or not is hidden from the player.
+                closed => if closed.is_flagged() {Cell::Mine} else {Cell::Closed},
+            };
+        }
+        board
+    }
+    /// Returns the knowledge about every tile, in row-major order.
+    #[inline(always)]
+    pub(crate) fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+    /// Returns a mutable reference to the knowledge about every tile, in row-major order.
+    #[cfg(feature = "generation")]
+    #[inline(always)]
+    pub(crate) fn cells_mut(&mut self) -> &mut [Cell] {
+        &mut self.cells
+    }
+    /// Returns the row-major indices of the neighbors of a tile which are inside the board.
+    pub(crate) fn neighbors(&self, index: usize) -> impl Iterator<Item = usize> {
+        let width = self.dimensions[0].get();
+        NeighborLocations::new(self.dimensions, [index % width, index / width])
+            .with_topology(self.topology)
+            .with_neighborhood(self.neighborhood)
+            .map(move |[x, y]| x + y * width)
+    }
+    /// Returns the tiles which can be proven to be safe or to contain a mine. Every tile is reported at most once, along with the simplest rule which proves it.
+    ///
+    /// If the total amount of mines on the field is known, it's used as an additional source of information. Nothing is returned if the information on the board is contradictory.
+    pub(crate) fn deduce(&self, total_mines: Option<usize>) -> Vec<RawDeduction> {
+        let mut found = BTreeMap::<usize, (bool, Rule, Vec<usize>)>::new();
+        let constraints = if let Some(constraints) = self.constraints() {constraints} else {return Vec::new()};
+
+        // Single-tile rule: a number which is already satisfied makes its other neighbors safe, and a number which needs all of its closed neighbors makes them all mines.
+        for constraint in &constraints {
+            if constraint.mines == 0 || constraint.mines == constraint.tiles.len() {
+                for &tile in &constraint.tiles {
+                    found.entry(tile)
+                        .or_insert_with(|| (constraint.mines != 0, Rule::SingleTile, alloc::vec![constraint.source]));
+                }
+            }
+        }
+        // Pair rule: if the difference between the mine counts of two overlapping constraints equals the amount of tiles only one of them has, those tiles are all mines and the ones only the other constraint has are all safe.
+        let mut by_tile = BTreeMap::<usize, Vec<usize>>::new();
+        for (i, constraint) in constraints.iter().enumerate() {
+            for &tile in &constraint.tiles {
+                by_tile.entry(tile).or_default().push(i);
+            }
+        }
+        for (a_index, a) in constraints.iter().enumerate() {
+            let mut overlapping = a.tiles.iter()
+                .flat_map(|tile| by_tile[tile].iter().copied())
+                .filter(|&b| b != a_index)
+                .collect::<Vec<_>>();
+            overlapping.sort_unstable();
+            overlapping.dedup();
+            for b in overlapping.into_iter().map(|b| &constraints[b]) {
+                let only_a = a.tiles.iter().filter(|t| !b.tiles.contains(t)).copied().collect::<Vec<_>>();
+                let only_b = b.tiles.iter().filter(|t| !a.tiles.contains(t)).copied().collect::<Vec<_>>();
+                if a.mines >= b.mines && a.mines - b.mines == only_a.len() {
+                    let verdicts = only_a.iter().map(|&t| (t, true))
+                        .chain(only_b.iter().map(|&t| (t, false)));
+                    for (tile, is_mine) in verdicts {
+                        found.entry(tile)
+                            .or_insert_with(|| (is_mine, Rule::Pair, alloc::vec![a.source, b.source]));
+                    }
+                }
+            }
+        }
+        // Global rule: if all mines are known, everything else is safe, and if the amount of closed tiles equals the amount of remaining mines, they're all mines.
+        if let Some(total_mines) = total_mines {
+            let known_mines = self.cells.iter().filter(|&&c| c == Cell::Mine).count();
+            let closed = self.cells.iter().filter(|&&c| c == Cell::Closed).count();
+            // More mines being flagged than there are on the field is a contradiction.
+            let remaining = if let Some(remaining) = total_mines.checked_sub(known_mines) {remaining} else {return Vec::new()};
+            if closed > 0 && (remaining == 0 || remaining == closed) {
+                for (i, _) in self.cells.iter().enumerate().filter(|(_, &c)| c == Cell::Closed) {
+                    found.entry(i)
+                        .or_insert_with(|| (remaining != 0, Rule::GlobalMineCount, Vec::new()));
+                }
+            }
+        }
+        found.into_iter()
+            .map(|(tile, (is_mine, rule, sources))| (tile, is_mine, rule, sources))
+            .collect()
+    }
+    /// Returns the constraints imposed by the open numbers, or `None` if a number has more flags around it than its value or fewer closed tiles than the mines it's missing.
+    pub(crate) fn constraints(&self) -> Option<Vec<Constraint>> {
+        let mut constraints = Vec::<Constraint>::new();
+        for (i, cell) in self.cells.iter().enumerate() {
+            if let Cell::Open(number) = *cell {
+                let mut tiles = Vec::with_capacity(8);
+                let mut mines = 0_usize;
+                for neighbor in self.neighbors(i) {
+                    match self.cells[neighbor] {
+                        Cell::Closed => tiles.push(neighbor),
+                        Cell::Mine => mines += 1,
+                        Cell::Open(_) | Cell::Void => {},
+                    }
+                }
+                let missing = usize::from(number).checked_sub(mines)?;
+                if missing > tiles.len() {return None}
+                if !tiles.is_empty() {
+                    constraints.push(Constraint {tiles, mines: missing, source: i});
+                }
+            }
+        }
+        // Numbers which impose the same constraint don't provide any new information, so only the first one of them is kept.
+        constraints.sort_unstable();
+        constraints.dedup_by(|a, b| a.tiles == b.tiles && a.mines == b.mines);
+        Some(constraints)
+    }
+}
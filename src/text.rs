@@ -0,0 +1,283 @@
+//! The plain text format for fields.
+//!
+//! Every row of the field is written on its own line, with one character per tile:
+//!
+//! | Character | Tile state |
+//! |-----------|------------|
+//! | `.` | `ClosedEmpty(Flag::NotFlagged)` |
+//! | `*` | `Mine(Flag::NotFlagged)` |
+//! | `f` | `ClosedEmpty(Flag::Flagged)` — a misplaced flag |
+//! | `F` | `Mine(Flag::Flagged)` |
+//! | `?` | `ClosedEmpty(Flag::Custom(_))` |
+//! | `!` | `Mine(Flag::Custom(_))` |
+//! | `0` | `OpenEmpty` |
+//! | `1`–`9` | `OpenNumber` |
+//! | `⑩`–`⑳`, `㉑`–`㉟`, `㊱`–`㊽` | `OpenNumber` from 10 to 48, for [neighborhoods][neighborhood] larger than the classic one |
+//! | `#` | `Void` |
+//!
+//! The numbers above 9 are written as the circled numbers from Unicode, so that they can't be confused with the other symbols. Since no neighborhood has more than 48 tiles, bigger numbers can't be written: displaying a field with one fails with `fmt::Error`, which makes `to_string` panic, while [`to_text`][m_to_text] returns a [`WriteFieldError`][wfe].
+//!
+//! The format is produced by the `Display` implementation of `Field` and by [`to_text`][m_to_text], and read by its `FromStr` implementation. Both `\n` and `\r\n` line endings are accepted, as well as a trailing line ending. Custom flags are all written as `?` or `!` and read as `Flag::Custom(Cf::default())`, which means that fields round-trip exactly as long as there's only one kind of custom flag, like the question mark in the classic game. The payloads of the tiles are not stored.
+//!
+//! # Usage
+//! ```
+//! # use sweeper::{Field, TileState};
+//! #
+//! let field: Field<(), ()> = "\
+//! 1F..
+//! 12*.
+//! 0?..".parse().unwrap();
+//! assert_eq!(field.dimensions()[0].get(), 4);
+//! assert!(field[[1, 0]].state.is_flagged());
+//! assert!(field[[2, 1]].state.is_mine());
+//! assert_eq!(field[[1, 2]].state.custom_flag(), Some(&()));
+//! // Displaying the field gives back the same text.
+//! assert_eq!(field.to_string(), "1F..\n12*.\n0?..");
+//! ```
+//!
+//! Fields with large neighborhoods use the circled numbers:
+//! ```
+//! # use sweeper::{Field, TileState, Flag, Neighborhood};
+//! # use core::num::NonZeroUsize;
+//! #
+//! let mut field = Field::<(), ()>::empty([NonZeroUsize::new(7).unwrap(); 2])
+//!     .with_neighborhood(Neighborhood::square(3).unwrap());
+//! for (location, tile) in field.enumerate_tiles_mut() {
+//!     if location != [3, 3] {
+//!         tile.state = TileState::Mine(Flag::NotFlagged);
+//!     }
+//! }
+//! field.open([3, 3]);
+//! let text = field.to_string();
+//! assert_eq!(text.lines().nth(3), Some("***㊽***"));
+//!
+//! // The text parses back into the same field.
+//! let parsed = text.parse::<Field<(), ()>>().unwrap()
+//!     .with_neighborhood(Neighborhood::square(3).unwrap());
+//! assert_eq!(format!("{:?}", parsed[[3, 3]].state), "OpenNumber(48)");
+//! assert_eq!(parsed.validate(), Ok(()));
+//! assert_eq!(parsed.to_string(), text);
+//! ```
+//!
+//! [neighborhood]: ../struct.Neighborhood.html "Neighborhood — describes which tiles around a tile count as its neighbors"
+//! [m_to_text]: ../struct.Field.html#method.to_text "to_text — writes the field in the text format"
+//! [wfe]: struct.WriteFieldError.html "WriteFieldError — the error type for writing a field in the text format"
+
+use core::{
+    fmt::{self, Formatter, Write},
+    num::{NonZeroUsize, NonZeroU8},
+    str::FromStr,
+};
+use alloc::{vec::Vec, string::{String, ToString}};
+use crate::{Field, FieldCoordinates, Tile, TileState, Flag};
+
+impl<Ct, Cf> Field<Ct, Cf> {
+    /// Writes the field in the [text format][text], or returns an error if an open tile shows a number which the format can't represent.
+    ///
+    /// This is the fallible version of `to_string`, which panics in that case.
+    ///
+    /// # Errors
+    /// Returns a [`WriteFieldError`][wfe] for the first tile in row-major order which shows a number above 48.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{Field, TileState, WriteFieldError};
+    /// # use core::num::{NonZeroUsize, NonZeroU8};
+    /// #
+    /// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(1).unwrap()]);
+    /// assert_eq!(field.to_text().as_deref(), Ok(".."));
+    ///
+    /// let number = NonZeroU8::new(49).unwrap();
+    /// field[[1, 0]].state = TileState::OpenNumber(number);
+    /// assert_eq!(field.to_text(), Err(WriteFieldError {location: [1, 0], number}));
+    /// ```
+    ///
+    /// [text]: text/index.html "text — the plain text format for fields"
+    /// [wfe]: text/struct.WriteFieldError.html "WriteFieldError — the error type for writing a field in the text format"
+    pub fn to_text(&self) -> Result<String, WriteFieldError> {
+        for (location, tile) in self.enumerate_tiles() {
+            if let Err(number) = tile_to_char(&tile.state) {
+                return Err(WriteFieldError {location, number});
+            }
+        }
+        Ok(self.to_string())
+    }
+}
+impl<Ct, Cf> fmt::Display for Field<Ct, Cf> {
+    /// Writes the field in the [text format][text].
+    ///
+    /// # Errors
+    /// Returns `fmt::Error` if an open tile shows a number above 48, which the format can't represent. This makes `to_string` panic on such fields; use [`to_text`][m_to_text] if they can occur.
+    ///
+    /// [text]: text/index.html "text — the plain text format for fields"
+    /// [m_to_text]: struct.Field.html#method.to_text "to_text — writes the field in the text format"
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let width = self.dimensions()[0].get();
+        for (i, tile) in self.all_tiles().enumerate() {
+            if i > 0 && i % width == 0 {
+                f.write_char('\n')?;
+            }
+            f.write_char(tile_to_char(&tile.state).map_err(|_| fmt::Error)?)?;
+        }
+        Ok(())
+    }
+}
+impl<Ct: Default, Cf: Default> FromStr for Field<Ct, Cf> {
+    type Err = ParseFieldError;
+    /// Reads a field in the [text format][text].
+    ///
+    /// [text]: text/index.html "text — the plain text format for fields"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_suffix('\n').map_or(s, |s| s.strip_suffix('\r').unwrap_or(s));
+        let mut storage = Vec::new();
+        let mut width = None;
+        let mut height = 0_usize;
+        for (y, line) in s.split('\n').enumerate() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            let mut row_width = 0_usize;
+            for (x, c) in line.chars().enumerate() {
+                let state = char_to_tile(c).ok_or(ParseFieldError {
+                    line: y + 1, column: x + 1,
+                    kind: ParseFieldErrorKind::UnexpectedCharacter(c),
+                })?;
+                storage.push(Tile {state, payload: Ct::default()});
+                row_width += 1;
+            }
+            let expected = *width.get_or_insert(row_width);
+            if row_width != expected {
+                return Err(ParseFieldError {
+                    line: y + 1, column: row_width.min(expected) + 1,
+                    kind: ParseFieldErrorKind::RowLengthMismatch {expected, found: row_width},
+                });
+            }
+            height += 1;
+        }
+        let dimensions = match (width.and_then(NonZeroUsize::new), NonZeroUsize::new(height)) {
+            (Some(width), Some(height)) => [width, height],
+            _ => return Err(ParseFieldError {line: 1, column: 1, kind: ParseFieldErrorKind::Empty}),
+        };
+        Ok(Self::from_dimensions_and_storage(dimensions, storage)
+            .expect("the storage was built row by row with the checked width"))
+    }
+}
+
+/// The first numbers of the blocks of circled numbers in Unicode, along with the code points of those numbers.
+const CIRCLED_NUMBERS: [(u8, u32); 3] = [(10, 0x2469), (21, 0x3251), (36, 0x32B1)];
+/// The biggest number which can appear on a field, which is the size of the biggest neighborhood.
+const MAX_NUMBER: u8 = 48;
+
+/// Returns the character for the specified tile state, or the number on it if it's too big to be written.
+fn tile_to_char<Cf>(state: &TileState<Cf>) -> Result<char, NonZeroU8> {
+    Ok(match state {
+        TileState::ClosedEmpty(Flag::NotFlagged) => '.',
+        TileState::Mine(Flag::NotFlagged) => '*',
+        TileState::ClosedEmpty(Flag::Flagged) => 'f',
+        TileState::Mine(Flag::Flagged) => 'F',
+        TileState::ClosedEmpty(Flag::Custom(_)) => '?',
+        TileState::Mine(Flag::Custom(_)) => '!',
+        TileState::OpenEmpty => '0',
+        TileState::OpenNumber(number) => number_to_char(number.get()).ok_or(*number)?,
+        TileState::Void => '#',
+    })
+}
+fn number_to_char(number: u8) -> Option<char> {
+    if number <= 9 {
+        return Some(char::from(b'0' + number));
+    }
+    if number > MAX_NUMBER {return None}
+    // Find the last block which starts at or before the number.
+    let &(first, code_point) = CIRCLED_NUMBERS.iter().rev().find(|&&(first, _)| first <= number)?;
+    char::from_u32(code_point + u32::from(number - first))
+}
+fn char_to_number(c: char) -> Option<NonZeroU8> {
+    // There are only a few dozen numbers, so looking the character up is simpler than inverting the mapping.
+    (1..=MAX_NUMBER).find(|&number| number_to_char(number) == Some(c)).and_then(NonZeroU8::new)
+}
+fn char_to_tile<Cf: Default>(c: char) -> Option<TileState<Cf>> {
+    Some(match c {
+        '.' => TileState::ClosedEmpty(Flag::NotFlagged),
+        '*' => TileState::Mine(Flag::NotFlagged),
+        'f' => TileState::ClosedEmpty(Flag::Flagged),
+        'F' => TileState::Mine(Flag::Flagged),
+        '?' => TileState::ClosedEmpty(Flag::Custom(Cf::default())),
+        '!' => TileState::Mine(Flag::Custom(Cf::default())),
+        '0' => TileState::OpenEmpty,
+        '#' => TileState::Void,
+        _ => TileState::OpenNumber(char_to_number(c)?),
+    })
+}
+
+/// The error type for reading a field in the [text format][text].
+///
+/// [text]: index.html "text — the plain text format for fields"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ParseFieldError {
+    /// The line at which the error occurred, starting from 1.
+    pub line: usize,
+    /// The column at which the error occurred, in characters, starting from 1.
+    pub column: usize,
+    /// The kind of the error.
+    pub kind: ParseFieldErrorKind,
+}
+impl fmt::Display for ParseFieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for ParseFieldError {}
+
+/// The kind of a [`ParseFieldError`][pfe].
+///
+/// [pfe]: struct.ParseFieldError.html "ParseFieldError — the error type for reading a field in the text format"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ParseFieldErrorKind {
+    /// The text is empty, while a field is at least 1x1.
+    Empty,
+    /// The character doesn't denote any tile state.
+    UnexpectedCharacter(char),
+    /// A row has a different length than the first one.
+    RowLengthMismatch {
+        /// The length of the first row.
+        expected: usize,
+        /// The length of the row which caused the error.
+        found: usize,
+    },
+}
+impl fmt::Display for ParseFieldErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f,
+                "the field is empty"
+            ),
+            Self::UnexpectedCharacter(c) => write!(f,
+                "unexpected character {c:?}"
+            ),
+            Self::RowLengthMismatch {expected, found} => write!(f,
+                "expected a row of {expected} tiles, found {found}"
+            ),
+        }
+    }
+}
+
+/// The error type for writing a field in the [text format][text], returned by [`to_text`][m_to_text] when an open tile shows a number which the format can't represent.
+///
+/// [text]: index.html "text — the plain text format for fields"
+/// [m_to_text]: ../struct.Field.html#method.to_text "to_text — writes the field in the text format"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WriteFieldError {
+    /// The location of the tile with the number.
+    pub location: FieldCoordinates,
+    /// The number which can't be written, which is always above 48.
+    pub number: NonZeroU8,
+}
+impl fmt::Display for WriteFieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f,
+            "the number {} on the open tile at {:?} can't be written in the text format",
+            self.number, self.location,
+        )
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for WriteFieldError {}
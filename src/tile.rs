@@ -12,11 +12,9 @@ use serde::{
     de::{self, Deserializer, Visitor, SeqAccess, MapAccess, EnumAccess, VariantAccess},
     Serialize, Deserialize,
 };
-use alloc::{
-    vec::Vec
-};
 use super::{
     Field, FieldCoordinates,
+    grid::{self, clearing_tiles},
 };
 
 /// A tile on a Minesweeper field.
@@ -141,7 +139,9 @@ pub enum TileState<Cf> {
     /// A tile which has been opened and has neighboring mines.
     OpenNumber(NonZeroU8),
     /// A tile which has a mine inside, and whether it's marked or not.
-    Mine(Flag<Cf>)
+    Mine(Flag<Cf>),
+    /// A hole in the field, used to give it an irregular shape. Such a tile never holds a mine, isn't counted as a neighbor, stops clearings from spreading through it and can't be opened or flagged.
+    Void,
 }
 impl<Cf> TileState<Cf> {
     /// Returns `true` if the tile is closed, `false` otherwise.
@@ -170,6 +170,11 @@ impl<Cf> TileState<Cf> {
             _ => false
         }
     }
+    /// Returns `true` if the tile is a hole in the field, `false` otherwise.
+    #[inline]
+    pub const fn is_void(&self) -> bool {
+        matches!(self, Self::Void)
+    }
     /// Returns `true` if clicking this tile does not end the game, `false` otherwise.
     #[inline(always)]
     pub fn is_safe(&self) -> bool {
@@ -219,7 +224,8 @@ impl<Cf> TileState<Cf> {
             Self::ClosedEmpty(_) => None,
             Self::OpenEmpty => Some(ClickOutcome::OpenClearing),
             Self::OpenNumber(_) => Some(ClickOutcome::Chord),
-            Self::Mine(_) => Some(ClickOutcome::Explosion)
+            Self::Mine(_) => Some(ClickOutcome::Explosion),
+            Self::Void => Some(ClickOutcome::Nothing),
         }
     }
 }
@@ -233,7 +239,7 @@ impl<Cf> Default for TileState<Cf> {
 impl<Cf> PartialEq<TileState<Cf>> for TileState<Cf> {
     /// Compares two tiles.
     ///
-    /// Two tiles are equal if they're both empty or they both contain a mine. Other factors, like the presence of a flag or amount of surrounding mines are not
+    /// Two tiles are equal if they're both empty, they both contain a mine or they're both void. Other factors, like the presence of a flag or amount of surrounding mines are not
     /// compared.
     fn eq(&self, other: &Self) -> bool {
         match self {
@@ -243,9 +249,10 @@ impl<Cf> PartialEq<TileState<Cf>> for TileState<Cf> {
                 }
                 else {false}
             },
+            Self::Void => other.is_void(),
             _ => {
                 match other {
-                    Self::Mine(_) => false,
+                    Self::Mine(_) | Self::Void => false,
                     _ => true
                 }
             },
@@ -277,6 +284,9 @@ where Cf: Serialize {
                 s.serialize_field(flag)?;
                 s.end()
             },
+            Self::Void => {
+                s.serialize_unit_variant("TileState", 4, "Void")
+            },
         }
     }
 }
@@ -284,11 +294,11 @@ where Cf: Serialize {
 impl<'de, Cf> Deserialize<'de> for TileState<Cf>
 where Cf: Deserialize<'de> {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
-        const VARIANTS: &[&str] = &["ClosedEmpty", "OpenEmpty", "OpenNumber", "Mine"];
+        const VARIANTS: &[&str] = &["ClosedEmpty", "OpenEmpty", "OpenNumber", "Mine", "Void"];
         #[derive(Deserialize)]
         #[repr(u8)]
         enum Tag {
-            ClosedEmpty, OpenEmpty, OpenNumber, Mine
+            ClosedEmpty, OpenEmpty, OpenNumber, Mine, Void
         }
 
         struct TsVisitor<Cf>(PhantomData<Cf>);
@@ -318,6 +328,10 @@ where Cf: Deserialize<'de> {
                     Tag::Mine => {
                         let flag = variant_data.tuple_variant(1, FlagVisitor(PhantomData))?;
                         Ok(TileState::Mine(flag))
+                    },
+                    Tag::Void => {
+                        variant_data.unit_variant()?;
+                        Ok(TileState::Void)
                     }
                 }
             }
@@ -378,77 +392,13 @@ where Cf: Deserialize<'de> {
     }
 }
 
-/// The implementation for the clearing traversal algorithm which works for both mutable usage and immutable usage.
-macro_rules! for_every_tile {
-    ($field:expr, $anchor_location:expr, $f:expr, $include_shore:expr) => {
-        // We're using this specific type as the type of a frame on the stack. It consists of two tuples:
-        // - The location at which the "painter" is currently located.
-        // - The state of the tile on the up, down, left and right directions.
-        //   True means we'd like to look there.
-        //   False means there's nothing of interest there, meaning that we've either looked there or there's a mine or a tile with a number.
-        type StackFrame = (FieldCoordinates, [bool; 4]);
-        // We're using a heap-based stack imposter instead of the thread stack to avoid
-        // overflowing. For large clearings, this will cause minor lag instead of
-        // crashing. For smaller ones, this will hardly make a difference at all, since
-        // we're preallocating it for a recursion depth of 10.
-        let mut stack = Vec::<StackFrame>::with_capacity(10);
-        let mut stack_top // Start at the anchor location.
-            = ($anchor_location, [true; 4]);
-        $f($field, stack_top.0); // Invoke the first run.
-        loop { // While we haven't emptied the stack...
-            let chosen_location
-               = if stack_top.1[0] {stack_top.1[0] = false; 0} // Up,
-            else if stack_top.1[1] {stack_top.1[1] = false; 1} // down,
-            else if stack_top.1[2] {stack_top.1[2] = false; 2} // left,
-            else if stack_top.1[3] {stack_top.1[3] = false; 3} // right.
-            // If we have nowhere to go, return to where we came from.
-            else if let Some(new_top) = stack.pop() {
-                stack_top = new_top;
-                continue;
-            // If we have nowhere to return, we can stop!
-            } else {break};
-
-            let location_to_peek // Now find the coordinates which we're about to peek.
-             =    if chosen_location == 0 {[stack_top.0[0], stack_top.0[1] + 1]}
-             else if chosen_location == 1 {[stack_top.0[0], stack_top.0[1] - 1]}
-             else if chosen_location == 2 {[stack_top.0[0] - 1, stack_top.0[1]]}
-             else if chosen_location == 3 {[stack_top.0[0] + 1, stack_top.0[1]]}
-             else {unreachable!()};
-
-            if let Some(outcome) = $field.peek(location_to_peek) {
-                match outcome {
-                    ClickOutcome::OpenClearing
-                    | ClickOutcome::Nothing => {
-                        // We found more clear land!
-                        // First of all, let's push the current state so that we can return to it later.
-                        stack.push(stack_top);
-                        // Then we'll set up the stack top for the next iteration.
-                        stack_top.0 = location_to_peek;
-                        stack_top.1 = [true; 4];
-                        $f($field, stack_top.0); // Run the closure, this is the point of our actions here.
-                    },
-                    ClickOutcome::Chord
-                  | ClickOutcome::Explosion => {}
-                    ClickOutcome::OpenNumber(_) => {
-                        if $include_shore {
-                            stack.push(stack_top);
-                            stack_top.0 = location_to_peek;
-                            stack_top.1 = [true; 4];
-                            $f($field, stack_top.0);
-                        }
-                    }
-                }
-            }
-        }
-    };
-}
 /// A reference to a clearing on the specified field.
 ///
 /// Clearings in Minesweeper are regions of tiles which can be safely opened by opening just one of its tiles. It's easier to demonstrate that with an example:
 ///
 /// ![Illustration of a clearing][img_clearing]
 ///
-/// As you can see, clearings mostly consist of tiles without numbers, i.e. tiles which don't have any mines in 8 directions around them. The tiles with numbers in 8 directions of all numberless tiles also can be included in the clearing, but they don't obey the rule presented above: opening any tile with a number doesn't make it unconditionally safe to open any of the surrounding tiles without looking at other tiles, flags, the number of mines and such.
+/// As you can see, clearings mostly consist of tiles without numbers, i.e. tiles which don't have any mines among the tiles in the field's neighborhood. The tiles with numbers in the neighborhood of the numberless tiles also can be included in the clearing, but they don't obey the rule presented above: opening any tile with a number doesn't make it unconditionally safe to open any of the surrounding tiles without looking at other tiles, flags, the number of mines and such.
 ///
 /// This structure consists of two elements which define a clearing on a field: a reference to the field and an **anchor location**. The former is self explanatory; the latter is the coordinates of a tile on a field which belongs to the clearing we want to refer to. Locating all tiles in a clearing uses a `Vec`-based recursive fill algorithm which starts from that exact location.
 ///
@@ -458,16 +408,12 @@ pub struct Clearing<'f, Ct, Cf> {
     anchor_location: FieldCoordinates
 }
 impl<'f, Ct, Cf> Clearing<'f, Ct, Cf> {
-    /// Returns a `Clearing` on the specified `Field`, or `None` if the location has 1 or more neighboring mines or is out of bounds.
+    /// Returns a `Clearing` on the specified `Field`, or `None` if the location is out of bounds, contains a mine, is [void][void] or has 1 or more neighboring mines.
+    ///
+    /// [void]: enum.TileState.html#variant.Void "TileState::Void — a hole in the field"
     pub fn new(field: &'f Field<Ct, Cf>, anchor_location: FieldCoordinates) -> Option<Self> {
-        if field.get(anchor_location).is_some() {
-            if field.count_neighboring_mines(anchor_location) > 0 {
-                None
-            } else {
-                Some(Self {
-                    field, anchor_location
-                })
-            }
+        if grid::is_clearing_anchor(field, anchor_location) {
+            Some(Self {field, anchor_location})
         } else {None}
     }
     /// Returns the field on which this clearing is located.
@@ -485,7 +431,10 @@ impl<'f, Ct, Cf> Clearing<'f, Ct, Cf> {
     #[cfg_attr(feature = "track_caller", track_caller)]
     pub fn for_every_tile<F>(self, include_shore: bool, mut f: F)
     where F: FnMut(&'f Field<Ct, Cf>, FieldCoordinates) {
-        for_every_tile!(self.field, self.anchor_location, f, include_shore);
+        let field: &'f Field<Ct, Cf> = self.field;
+        for location in clearing_tiles(field, self.anchor_location, include_shore, false) {
+            f(field, location);
+        }
     }
     /// Returns the size of the clearing, in tiles. Optionally can include the "shore" (tiles with numbers) as a part of the clearing.
     #[cfg_attr(feature = "track_caller", track_caller)]
@@ -521,16 +470,12 @@ pub struct ClearingMut<'f, Ct, Cf> {
     anchor_location: FieldCoordinates
 }
 impl<'f, Ct, Cf> ClearingMut<'f, Ct, Cf> {
-    /// Returns a `ClearingMut` on the specified `Field`, or `None` if the location has 1 or more neighboring mines or is out of bounds.
+    /// Returns a `ClearingMut` on the specified `Field`, or `None` if the location is out of bounds, contains a mine, is [void][void] or has 1 or more neighboring mines.
+    ///
+    /// [void]: enum.TileState.html#variant.Void "TileState::Void — a hole in the field"
     pub fn new(field: &'f mut Field<Ct, Cf>, anchor_location: FieldCoordinates) -> Option<Self> {
-        if field.get(anchor_location).is_some() {
-            if field.count_neighboring_mines(anchor_location) > 0 {
-                None
-            } else {
-                Some(Self {
-                    field, anchor_location
-                })
-            }
+        if grid::is_clearing_anchor(field, anchor_location) {
+            Some(Self {field, anchor_location})
         } else {None}
     }
     /// Returns the field on which this clearing is located.
@@ -550,7 +495,10 @@ impl<'f, Ct, Cf> ClearingMut<'f, Ct, Cf> {
     #[cfg_attr(feature = "track_caller", track_caller)]
     pub fn for_every_tile<F>(self, include_shore: bool, mut f: F)
     where F: FnMut(&'f Field<Ct, Cf>, FieldCoordinates) {
-        for_every_tile!(self.field, self.anchor_location, f, include_shore);
+        let field: &'f Field<Ct, Cf> = self.field;
+        for location in clearing_tiles(field, self.anchor_location, include_shore, false) {
+            f(field, location);
+        }
     }
     /// Executes the specified closure on every tile inside the clearing. Optionally can include the "shore" (tiles with numbers) as a part of the clearing.
     ///
@@ -558,7 +506,10 @@ impl<'f, Ct, Cf> ClearingMut<'f, Ct, Cf> {
     #[cfg_attr(feature = "track_caller", track_caller)]
     pub fn for_every_tile_mut<F>(self, include_shore: bool, mut f: F)
     where F: FnMut(&mut Field<Ct, Cf>, FieldCoordinates) {
-        for_every_tile!(self.field, self.anchor_location, f, include_shore);
+        // The clearing is located before running the closure, so that modifying the field doesn't affect its shape.
+        for location in clearing_tiles(self.field, self.anchor_location, include_shore, false) {
+            f(self.field, location);
+        }
     }
     /// Returns the size of the clearing, in tiles. Optionally can include the "shore" (tiles with numbers) as a part of the clearing.
     ///
@@ -583,21 +534,51 @@ impl<'f, Ct, Cf> ClearingMut<'f, Ct, Cf> {
     }
     /// Fully opens the clearing on the field and returns the amount of tiles opened. Optionally can include the "shore" (tiles with numbers) as a part of the clearing.
     ///
-    /// The first number is the amount of tiles which were opened, and the second one is the total size of the clearing, which includes both previously closed and previously open tiles.
+    /// The first number is the amount of tiles which were opened, and the second one is the total size of the clearing, which includes both previously closed and previously open tiles. The tiles on the shore are opened as number tiles.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::{Field, TileState, Flag};
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// // Open the clearing from every possible anchor of every mine layout on fields up to 4x3.
+    /// for width in 1..=4 {
+    ///     for height in 1..=3 {
+    ///         let area = width * height;
+    ///         for layout in 0..1_u32 << area {
+    ///             let mut field = Field::<(), ()>::empty([NonZeroUsize::new(width).unwrap(), NonZeroUsize::new(height).unwrap()]);
+    ///             for i in 0..area {
+    ///                 if layout & 1 << i != 0 {
+    ///                     field[[i % width, i / width]].state = TileState::Mine(Flag::NotFlagged);
+    ///                 }
+    ///             }
+    ///             for anchor in 0..area {
+    ///                 let anchor = [anchor % width, anchor / width];
+    ///                 let mut field = field.clone();
+    ///                 let clearing = match field.clearing_mut(anchor) {
+    ///                     Some(clearing) => clearing,
+    ///                     None => continue,
+    ///                 };
+    ///                 let (opened, total) = clearing.open(true);
+    ///                 assert_eq!(opened, total.get());
+    ///                 assert_eq!(field.count_open_tiles(), opened);
+    ///                 // All numbers are correct, and every tile without a number has all of its neighbors open.
+    ///                 assert_eq!(field.validate(), Ok(()));
+    ///                 for y in 0..height {
+    ///                     for x in 0..width {
+    ///                         if let TileState::OpenEmpty = field[[x, y]].state {
+    ///                             assert!(field.neighbors([x, y]).all(|(_, tile)| tile.state.is_open()));
+    ///                         }
+    ///                     }
+    ///                 }
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
     pub fn open(self, include_shore: bool) -> (usize, NonZeroUsize) {
-        let [mut opened_size, mut total_size] = [0_usize; 2];
-
-        self.for_every_tile_mut(include_shore, |field, location| {
-            total_size += 1;
-            if let TileState::ClosedEmpty(_) = field[location].state {
-                field[location].state = TileState::OpenEmpty;
-                opened_size += 1;
-            }
-        });
-
-        (opened_size, NonZeroUsize::new(total_size)
+        grid::open_clearing(self.field, self.anchor_location, include_shore, false)
             .expect("unexpected zero clearing size (nonzero clearing size is a safety guarantee)")
-        )
     }
 }
 impl<'f, Ct, Cf> From<ClearingMut<'f, Ct, Cf>> for Clearing<'f, Ct, Cf> {
@@ -0,0 +1,104 @@
+//! The shape of the surface a field is laid out on.
+
+use core::convert::TryFrom;
+#[cfg(feature = "serialization")]
+use serde::{Serialize, Deserialize};
+use crate::{FieldDimensions, FieldCoordinates};
+
+/// Describes which edges of a field touch each other.
+///
+/// On a bounded field, the tiles on the border simply have fewer neighbors. The other topologies glue the opposite edges of the field together, so that stepping over an edge leads to the tile on the other side, which gives every tile as many neighbors as there are tiles in the field's neighborhood on fields which are large enough for the neighborhood to fit without overlapping itself, i.e. at least 3 tiles wide and high for the classic one. On narrower fields, several directions can lead to the same tile, which is then only counted once, and a tile is never its own neighbor.
+///
+/// The topology affects everything which looks at the neighbors of a tile: the numbers on open tiles, chords, clearings, the 3BV value and the [neighbor iterators][nl]. Only the Serde format stores the topology of a field — the text, binary and share code formats always load bounded fields.
+///
+/// # Usage
+/// ```
+/// # use sweeper::{Field, TileState, Flag, Topology};
+/// # use core::num::NonZeroUsize;
+/// #
+/// let mut field = Field::<(), ()>::empty([NonZeroUsize::new(5).unwrap(); 2])
+///     .with_topology(Topology::Toroidal);
+/// field[[4, 4]].state = TileState::Mine(Flag::NotFlagged);
+/// // The bottom right corner touches the top left one.
+/// assert_eq!(field.count_neighboring_mines([0, 0]), 1);
+/// assert_eq!(field.neighbors([2, 0]).count(), 8);
+///
+/// // On a cylinder, only the left and right edges touch.
+/// let field = field.with_topology(Topology::Cylindrical);
+/// assert_eq!(field.count_neighboring_mines([0, 4]), 1);
+/// assert_eq!(field.count_neighboring_mines([0, 0]), 0);
+/// ```
+///
+/// [nl]: iter/struct.NeighborLocations.html "NeighborLocations — iterates over the locations of the neighbors of a tile"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub enum Topology {
+    /// A plain rectangle: none of the edges touch each other.
+    ///
+    /// Returned by the `Default` trait implementation.
+    Bounded,
+    /// The left edge touches the right one, like on the side of a cylinder. The top and bottom edges are bounded.
+    Cylindrical,
+    /// The left edge touches the right one and the top edge touches the bottom one, like on the surface of a donut.
+    Toroidal,
+}
+impl Topology {
+    /// Returns `true` if stepping over the left or right edge leads to the other side of the field, `false` otherwise.
+    #[inline]
+    pub const fn wraps_horizontally(self) -> bool {
+        match self {
+            Self::Bounded => false,
+            Self::Cylindrical | Self::Toroidal => true,
+        }
+    }
+    /// Returns `true` if stepping over the top or bottom edge leads to the other side of the field, `false` otherwise.
+    #[inline]
+    pub const fn wraps_vertically(self) -> bool {
+        match self {
+            Self::Bounded | Self::Cylindrical => false,
+            Self::Toroidal => true,
+        }
+    }
+    /// Returns the location of the tile which is the specified amount of tiles away from the specified one, or `None` if it's outside the field or the starting tile itself is outside the field.
+    ///
+    /// # Usage
+    /// ```
+    /// # use sweeper::Topology;
+    /// # use core::num::NonZeroUsize;
+    /// #
+    /// let dimensions = [NonZeroUsize::new(9).unwrap(); 2];
+    /// assert_eq!(Topology::Bounded.offset(dimensions, [0, 0], [-1, 0]), None);
+    /// assert_eq!(Topology::Cylindrical.offset(dimensions, [0, 0], [-1, 0]), Some([8, 0]));
+    /// assert_eq!(Topology::Cylindrical.offset(dimensions, [0, 0], [0, -1]), None);
+    /// assert_eq!(Topology::Toroidal.offset(dimensions, [8, 8], [1, 1]), Some([0, 0]));
+    /// ```
+    pub fn offset(self, dimensions: FieldDimensions, location: FieldCoordinates, offset: [isize; 2]) -> Option<FieldCoordinates> {
+        let x = shift(location[0], offset[0], dimensions[0].get(), self.wraps_horizontally())?;
+        let y = shift(location[1], offset[1], dimensions[1].get(), self.wraps_vertically())?;
+        Some([x, y])
+    }
+}
+impl Default for Topology {
+    /// Returns the `Bounded` topology.
+    #[inline(always)]
+    fn default() -> Self {
+        Self::Bounded
+    }
+}
+
+/// Moves a coordinate by the specified offset along an axis of the specified length, wrapping around if requested.
+fn shift(coordinate: usize, offset: isize, length: usize, wrap: bool) -> Option<usize> {
+    if coordinate >= length {return None}
+    if wrap {
+        // Both values are smaller than the length, which fits into isize, so the sum can't overflow.
+        let offset = usize::try_from(offset.rem_euclid(isize::try_from(length).ok()?)).ok()?;
+        Some((coordinate + offset) % length)
+    } else {
+        let shifted = if offset < 0 {
+            coordinate.checked_sub(offset.unsigned_abs())
+        } else {
+            coordinate.checked_add(offset.unsigned_abs())
+        };
+        shifted.filter(|&shifted| shifted < length)
+    }
+}